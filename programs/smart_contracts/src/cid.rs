@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CidError;
+
+// Multicodec content types accepted in CIDv1
+const CODEC_RAW: u64 = 0x55;
+const CODEC_DAG_PB: u64 = 0x70;
+const CODEC_DAG_CBOR: u64 = 0x71;
+const CODEC_DAG_JSON: u64 = 0x0129;
+
+// Multihash functions with a 32-byte digest
+const HASH_SHA2_256: u64 = 0x12;
+const HASH_BLAKE3: u64 = 0x1e;
+const HASH_BLAKE2B_256: u64 = 0xb220;
+
+const DIGEST_LEN: usize = 32;
+
+// Longest varint the multiformats spec allows
+const MAX_VARINT_LEN: usize = 9;
+
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decoded content identifier. Off-chain fetchers hash the content with
+/// `hash_code` and compare it against `digest`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub struct Cid {
+    pub version: u8,
+    pub codec: u64,
+    pub hash_code: u64,
+    pub digest: [u8; DIGEST_LEN],
+}
+
+impl Cid {
+    /// Parses a CIDv0 (`Qm...`) or a CIDv1 in base32 (`b`), base58btc (`z`)
+    /// or base16 (`f`) multibase.
+    pub fn parse(cid: &str) -> Result<Self> {
+        // CIDv0 is a bare base58btc sha2-256 multihash, always 46 chars
+        if cid.len() == 46 && cid.starts_with("Qm") {
+            let bytes = decode_base58(cid)?;
+            let mut cursor = bytes.as_slice();
+            let (hash_code, digest) = read_multihash(&mut cursor)?;
+
+            require!(hash_code == HASH_SHA2_256, CidError::UnsupportedHash);
+            require!(cursor.is_empty(), CidError::InvalidCidEncoding);
+
+            return Ok(Self {
+                version: 0,
+                codec: CODEC_DAG_PB,
+                hash_code,
+                digest,
+            });
+        }
+
+        let encoded = cid.get(1..).unwrap_or_default();
+        let bytes = match cid.as_bytes().first() {
+            Some(b'b') => decode_base32(encoded)?,
+            Some(b'z') => decode_base58(encoded)?,
+            Some(b'f') => decode_base16(encoded)?,
+            _ => return Err(CidError::UnsupportedMultibase.into()),
+        };
+
+        let mut cursor = bytes.as_slice();
+
+        let version = read_varint(&mut cursor)?;
+        require!(version == 1, CidError::UnsupportedCidVersion);
+
+        let codec = read_varint(&mut cursor)?;
+        require!(
+            matches!(codec, CODEC_RAW | CODEC_DAG_PB | CODEC_DAG_CBOR | CODEC_DAG_JSON),
+            CidError::UnsupportedCodec
+        );
+
+        let (hash_code, digest) = read_multihash(&mut cursor)?;
+        require!(cursor.is_empty(), CidError::InvalidCidEncoding);
+
+        Ok(Self {
+            version: 1,
+            codec,
+            hash_code,
+            digest,
+        })
+    }
+}
+
+fn read_multihash(cursor: &mut &[u8]) -> Result<(u64, [u8; DIGEST_LEN])> {
+    let hash_code = read_varint(cursor)?;
+    require!(
+        matches!(hash_code, HASH_SHA2_256 | HASH_BLAKE3 | HASH_BLAKE2B_256),
+        CidError::UnsupportedHash
+    );
+
+    let len = read_varint(cursor)?;
+    require!(len == DIGEST_LEN as u64, CidError::UnsupportedHash);
+    require!(cursor.len() >= DIGEST_LEN, CidError::InvalidCidEncoding);
+
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(&cursor[..DIGEST_LEN]);
+    *cursor = &cursor[DIGEST_LEN..];
+
+    Ok((hash_code, digest))
+}
+
+// Unsigned LEB128, rejecting overlong encodings
+fn read_varint(cursor: &mut &[u8]) -> Result<u64> {
+    let mut value: u64 = 0;
+
+    for (i, byte) in cursor.iter().take(MAX_VARINT_LEN).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            require!(i == 0 || *byte != 0, CidError::InvalidCidEncoding);
+            *cursor = &cursor[i + 1..];
+            return Ok(value);
+        }
+    }
+
+    Err(CidError::InvalidCidEncoding.into())
+}
+
+// RFC 4648 lowercase base32 without padding
+fn decode_base32(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in encoded.bytes() {
+        let index = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(CidError::InvalidCidEncoding)?;
+
+        buffer = (buffer << 5) | index as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    // Leftover bits are padding: fewer than one character's worth, all zero
+    require!(bits < 5 && buffer == 0, CidError::InvalidCidEncoding);
+
+    Ok(bytes)
+}
+
+fn decode_base58(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len());
+
+    for c in encoded.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(CidError::InvalidCidEncoding)? as u32;
+
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    // Each leading '1' encodes a leading zero byte
+    let zeros = encoded.bytes().take_while(|&c| c == b'1').count();
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes);
+
+    Ok(decoded)
+}
+
+fn decode_base16(encoded: &str) -> Result<Vec<u8>> {
+    let nibble = |c: u8| -> Result<u8> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            _ => Err(CidError::InvalidCidEncoding.into()),
+        }
+    };
+
+    let pairs = encoded.as_bytes().chunks_exact(2);
+    require!(pairs.remainder().is_empty(), CidError::InvalidCidEncoding);
+
+    pairs
+        .map(|pair| Ok(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // sha256("nodara")
+    const DIGEST: &str = "b5185d978ec5fb38c1f08742279caac2b9e358ddf58c5dd94011794213ecb7ae";
+
+    fn digest() -> [u8; DIGEST_LEN] {
+        decode_base16(DIGEST).unwrap().try_into().unwrap()
+    }
+
+    fn error_code(cid: &str) -> u32 {
+        match Cid::parse(cid).unwrap_err() {
+            Error::AnchorError(err) => err.error_code_number,
+            Error::ProgramError(err) => panic!("unexpected program error {err:?}"),
+        }
+    }
+
+    fn code(err: CidError) -> u32 {
+        err as u32 + anchor_lang::error::ERROR_CODE_OFFSET
+    }
+
+    #[test]
+    fn parses_cid_v0() {
+        let cid = Cid::parse("QmaXa3xoVC8UwBPtLt15fG6D1W8LTX8886Ti5hJsJVZHJu").unwrap();
+        assert_eq!(cid.version, 0);
+        assert_eq!(cid.codec, CODEC_DAG_PB);
+        assert_eq!(cid.hash_code, HASH_SHA2_256);
+        assert_eq!(cid.digest, digest());
+    }
+
+    #[test]
+    fn parses_cid_v1_multibases() {
+        let base32 = Cid::parse("bafkreifvdbozpdwf7m4md4ehiitzzkwcxhrvrxpvrro5sqarpfbbh3fxvy").unwrap();
+        assert_eq!((base32.version, base32.codec, base32.hash_code), (1, CODEC_RAW, HASH_SHA2_256));
+        assert_eq!(base32.digest, digest());
+
+        let base58 = Cid::parse("zdqAuXwNPbRWGg8U3qgXrBf9itKpD45eRGXQzDNimGBYFreMB").unwrap();
+        assert_eq!((base58.codec, base58.hash_code), (CODEC_DAG_CBOR, HASH_BLAKE3));
+        assert_eq!(base58.digest, digest());
+
+        let base16 = Cid::parse(&format!("f01701220{DIGEST}")).unwrap();
+        assert_eq!((base16.codec, base16.hash_code), (CODEC_DAG_PB, HASH_SHA2_256));
+        assert_eq!(base16.digest, digest());
+    }
+
+    #[test]
+    fn rejects_non_canonical_base32() {
+        // Last character carries non-zero padding bits
+        assert_eq!(
+            error_code("bafkreifvdbozpdwf7m4md4ehiitzzkwcxhrvrxpvrro5sqarpfbbh3fxvz"),
+            code(CidError::InvalidCidEncoding)
+        );
+        // Trailing character that encodes no data
+        assert_eq!(
+            error_code("bafkreifvdbozpdwf7m4md4ehiitzzkwcxhrvrxpvrro5sqarpfbbh3fxvya"),
+            code(CidError::InvalidCidEncoding)
+        );
+        // Uppercase is a different multibase
+        assert_eq!(
+            error_code("bAFKREIFVDBOZPDWF7M4MD4EHIITZZKWCXHRVRXPVRRO5SQARPFBBH3FXVY"),
+            code(CidError::InvalidCidEncoding)
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_cids() {
+        assert_eq!(error_code("mAXASIA"), code(CidError::UnsupportedMultibase));
+        assert_eq!(
+            error_code(&format!("f02701220{DIGEST}")),
+            code(CidError::UnsupportedCidVersion)
+        );
+        assert_eq!(
+            error_code(&format!("f01501220{DIGEST}")),
+            code(CidError::UnsupportedCodec)
+        );
+        assert_eq!(
+            error_code(&format!("f01701320{DIGEST}")),
+            code(CidError::UnsupportedHash)
+        );
+        // Digest one byte short
+        assert_eq!(
+            error_code(&format!("f01701220{}", &DIGEST[2..])),
+            code(CidError::InvalidCidEncoding)
+        );
+    }
+
+    #[test]
+    fn reads_varints() {
+        let mut cursor: &[u8] = &[0x80, 0x01, 0xff];
+        assert_eq!(read_varint(&mut cursor).unwrap(), 128);
+        assert_eq!(cursor, &[0xff]);
+
+        let mut cursor: &[u8] = &[0xa0, 0xe4, 0x02];
+        assert_eq!(read_varint(&mut cursor).unwrap(), HASH_BLAKE2B_256);
+
+        // Overlong encoding of zero
+        let mut cursor: &[u8] = &[0x80, 0x00];
+        assert!(read_varint(&mut cursor).is_err());
+
+        // Unterminated
+        let mut cursor: &[u8] = &[0x80; MAX_VARINT_LEN];
+        assert!(read_varint(&mut cursor).is_err());
+    }
+}
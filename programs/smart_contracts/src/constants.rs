@@ -1,2 +1,52 @@
 pub const MAX_INPUT_SIZE: usize = 512;
 pub const MAX_PAYLOAD_SIZE: usize = 512;
+pub const MAX_DENYLIST_REASON_LEN: usize = 100;
+
+// Trust scores are expressed in basis points (0 - 10_000)
+pub const TRUST_SCORE_MAX: u16 = 10_000;
+pub const TRUST_SCORE_INITIAL: u16 = 5_000;
+// Weight given to the latest outcome; older outcomes decay by the remainder
+pub const TRUST_SCORE_WEIGHT_BPS: u64 = 1_000;
+
+// Platform fee charged on deposits (6.9%)
+pub const PLATFORM_FEE_NUMERATOR: u64 = 69;
+pub const PLATFORM_FEE_DENOMINATOR: u64 = 1000;
+
+// Referrers receive at most half of the platform fee (basis points)
+pub const MAX_REFERRAL_SHARE_BPS: u16 = 5_000;
+
+// Reward multipliers are expressed in basis points (10_000 = 1.0x)
+pub const BASE_REWARD_MULTIPLIER_BPS: u16 = 10_000;
+pub const MAX_REWARD_MULTIPLIER_BPS: u16 = 15_000;
+
+// Share of values dropped from each end by the trimmed mean aggregator
+pub const AGGREGATION_TRIM_BPS: usize = 1_000;
+// Values are collected on-chain across batches, which bounds aggregated tasks
+pub const MAX_AGGREGATED_RESPONSES: usize = 512;
+
+// Disputes can be opened for 24 hours after a response is verified, creators
+// can choose a window of up to a week before their task receives responses
+pub const DISPUTE_WINDOW_SECONDS: i64 = 86_400;
+pub const MAX_DISPUTE_WINDOW_SECONDS: i64 = 604_800;
+// Bond required from challengers other than the task creator (0.01 SOL)
+pub const MIN_DISPUTE_BOND: u64 = 10_000_000;
+
+// Juror arbitration
+pub const MAX_JURORS: usize = 64;
+pub const JURY_SIZE: usize = 3;
+pub const MIN_JUROR_STAKE: u64 = 100_000_000;
+pub const JURY_COMMIT_PERIOD_SECONDS: i64 = 86_400;
+pub const JURY_REVEAL_PERIOD_SECONDS: i64 = 86_400;
+// Jurors are drawn from the hash of a slot this far past the jury request,
+// which the requester cannot know when they commit to escalating
+pub const JURY_SEED_DELAY_SLOTS: u64 = 8;
+// Leaving jurors stay slashable until any jury they sat on can be settled
+pub const JUROR_UNBONDING_PERIOD_SECONDS: i64 = 259_200;
+// Share of the dispute bond paid to jurors who vote with the majority
+pub const JUROR_REWARD_BPS: u64 = 2_000;
+// Share of stake slashed from jurors who don't reveal or vote with the minority
+pub const JUROR_SLASH_BPS: u64 = 1_000;
+
+// Task pipelines
+pub const MAX_PREREQUISITES: usize = 4;
+pub const MAX_PREREQUISITE_OUTPUTS: usize = 8;
@@ -58,6 +58,72 @@ pub enum TaskError {
 
     #[msg("Not enough verified responses to complete task")]
     NotEnoughVerifiedResponses,
+
+    #[msg("Response already rejected")]
+    ResponseAlreadyRejected,
+
+    #[msg("Trust score must not exceed 10000")]
+    InvalidTrustScore,
+
+    #[msg("Responder trust score is below the task minimum")]
+    TrustScoreTooLow,
+
+    #[msg("Responder is not on the task allowlist")]
+    NotAllowlisted,
+
+    #[msg("Task already has responses")]
+    TaskHasResponses,
+
+    #[msg("Task requires commit-reveal submissions")]
+    CommitRevealRequired,
+
+    #[msg("Task does not use commit-reveal submissions")]
+    CommitRevealNotEnabled,
+
+    #[msg("Reveal window is not open")]
+    RevealWindowNotOpen,
+
+    #[msg("Revealed CID does not match the commitment")]
+    ResponseCommitmentMismatch,
+
+    #[msg("Response has not been revealed")]
+    ResponseNotRevealed,
+
+    #[msg("Response was revealed in time")]
+    ResponseRevealed,
+
+    #[msg("Aggregated tasks cannot use commit-reveal submissions")]
+    IncompatibleTaskModes,
+
+    #[msg("Task expects a numeric value with each response")]
+    MissingNumericValue,
+
+    #[msg("Task does not aggregate responses")]
+    AggregationNotEnabled,
+
+    #[msg("Task responses already aggregated")]
+    AlreadyAggregated,
+
+    #[msg("Responder reached the per-responder response limit")]
+    ResponderLimitReached,
+
+    #[msg("Max responses cannot be lowered below the responses received")]
+    MaxResponsesBelowReceived,
+
+    #[msg("Reward cannot change once responses have been received")]
+    RewardChangeNotAllowed,
+
+    #[msg("Task was cancelled by its creator")]
+    TaskCancelled,
+
+    #[msg("Every task response must be supplied exactly once")]
+    InvalidResponseAccounts,
+
+    #[msg("Task responses have not been aggregated yet")]
+    NotAggregated,
+
+    #[msg("Too many responses to aggregate")]
+    TooManyAggregatedResponses,
 }
 
 #[error_code]
@@ -79,6 +145,195 @@ pub enum RewardError {
 
     #[msg("Reward amount exceeds the reward per response")]
     ExcessiveRewardAmount,
+
+    #[msg("Reward multiplier must be between 10000 and 15000 basis points")]
+    InvalidMultiplier,
+
+    #[msg("Reward already disbursed for this response")]
+    RewardAlreadyPaid,
+
+    #[msg("Vault lamports do not cover its tracked balance")]
+    VaultBalanceMismatch,
+
+    #[msg("Sponsor has no refund to claim")]
+    NoSponsorRefund,
+
+    #[msg("Vault holds no surplus to withdraw")]
+    NoSurplus,
+
+    #[msg("Creator profile does not belong to the task creator")]
+    InvalidCreatorProfile,
+}
+
+#[error_code]
+pub enum DenylistError {
+    #[msg("Responder is on the denylist")]
+    ResponderDenylisted,
+
+    #[msg("Denylist reason is too long")]
+    ReasonTooLong,
+}
+
+#[error_code]
+pub enum DisputeError {
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Dispute bond is below the minimum")]
+    InsufficientBond,
+
+    #[msg("Response payout is frozen by an open dispute")]
+    PayoutFrozen,
+
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+
+    #[msg("Dispute has been escalated to a jury")]
+    JuryAssigned,
+
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Dispute window is out of range")]
+    InvalidDisputeWindow,
+}
+
+#[error_code]
+pub enum ArbitrationError {
+    #[msg("Juror pool is full")]
+    JurorPoolFull,
+
+    #[msg("Juror stake is below the minimum")]
+    InsufficientStake,
+
+    #[msg("Not enough registered jurors to form a jury")]
+    NotEnoughJurors,
+
+    #[msg("Signer was not selected for this jury")]
+    NotSelectedJuror,
+
+    #[msg("Commit phase has closed")]
+    CommitPhaseClosed,
+
+    #[msg("Reveal phase is not open")]
+    RevealPhaseNotOpen,
+
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Voting has not finished yet")]
+    VotingNotFinished,
+
+    #[msg("Jury has not resolved the dispute yet")]
+    JuryNotResolved,
+
+    #[msg("Juror has already been settled")]
+    JurorAlreadySettled,
+
+    #[msg("Juror still has active cases")]
+    JurorHasActiveCases,
+
+    #[msg("Juror is already unbonding")]
+    JurorUnbonding,
+
+    #[msg("Juror stake is still unbonding")]
+    UnbondingNotFinished,
+
+    #[msg("Jury has not been requested for this dispute")]
+    JuryNotRequested,
+
+    #[msg("Jury has already been requested for this dispute")]
+    JuryAlreadyRequested,
+
+    #[msg("Jury seed slot has not been reached yet")]
+    JurySeedPending,
+
+    #[msg("Jury seed slot hash has expired, request the jury again")]
+    JurySeedExpired,
+}
+
+#[error_code]
+pub enum TemplateError {
+    #[msg("Template schedule is invalid")]
+    InvalidSchedule,
+
+    #[msg("All template epochs have been opened")]
+    AllEpochsOpened,
+
+    #[msg("Next epoch has not started yet")]
+    EpochNotStarted,
+
+    #[msg("Template vault cannot fund the next epoch")]
+    InsufficientTemplateFunds,
+
+    #[msg("Template still has epochs with open vaults")]
+    EpochsStillOpen,
+
+    #[msg("Next epoch's window has already passed")]
+    EpochWindowPassed,
+
+    #[msg("Next epoch's window has not passed yet")]
+    EpochWindowOpen,
+}
+
+#[error_code]
+pub enum PipelineError {
+    #[msg("Too many prerequisite tasks")]
+    TooManyPrerequisites,
+
+    #[msg("Prerequisite task is invalid")]
+    InvalidPrerequisite,
+
+    #[msg("Prerequisite task accounts are missing")]
+    PrerequisiteMissing,
+
+    #[msg("Prerequisite task is not complete")]
+    PrerequisiteNotComplete,
+
+    #[msg("Prerequisite response has not been accepted")]
+    OutputNotAccepted,
+
+    #[msg("Prerequisite output is already linked")]
+    OutputAlreadyLinked,
+
+    #[msg("Too many prerequisite outputs")]
+    TooManyOutputs,
+}
+
+#[error_code]
+pub enum ReferralError {
+    #[msg("Referral share exceeds the maximum")]
+    InvalidReferralShare,
+
+    #[msg("Creator already has a referrer")]
+    ReferrerAlreadySet,
+
+    #[msg("Creators cannot refer themselves")]
+    SelfReferral,
+
+    #[msg("Referrer accrual account is missing or does not match")]
+    ReferrerAccountMismatch,
+
+    #[msg("No referral fees to claim")]
+    NothingToClaim,
+}
+
+#[error_code]
+pub enum CidError {
+    #[msg("CID multibase prefix is not supported")]
+    UnsupportedMultibase,
+
+    #[msg("CID is not correctly encoded")]
+    InvalidCidEncoding,
+
+    #[msg("CID version is not supported")]
+    UnsupportedCidVersion,
+
+    #[msg("CID content codec is not supported")]
+    UnsupportedCodec,
+
+    #[msg("CID hash must be a supported 32-byte multihash")]
+    UnsupportedHash,
 }
 
 #[error_code]
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::cid::Cid;
+
+#[event]
+pub struct ResponderDenylisted {
+    pub responder: Pubkey,
+    pub added_by: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResponderRemovedFromDenylist {
+    pub responder: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dispute: Pubkey,
+    pub response: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute: Pubkey,
+    pub response: Pubkey,
+    pub in_favor_of_challenger: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JuryRequested {
+    pub dispute: Pubkey,
+    pub seed_slot: u64,
+}
+
+#[event]
+pub struct JurySummoned {
+    pub dispute: Pubkey,
+    pub jurors: Vec<Pubkey>,
+    pub commit_deadline: i64,
+}
+
+#[event]
+pub struct TaskUpdated {
+    pub task: Pubkey,
+    pub old_reward_per_response: u64,
+    pub new_reward_per_response: u64,
+    pub old_max_responses: u16,
+    pub new_max_responses: u16,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub old_cid: Cid,
+    pub new_cid: Cid,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultReconciled {
+    pub vault: Pubkey,
+    pub tracked_balance: u64,
+    pub swept: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TaskSponsored {
+    pub task: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64, // Net of the platform fee
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorRefundClaimed {
+    pub task: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResponseTipped {
+    pub task: Pubkey,
+    pub response: Pubkey,
+    pub responder: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64, // Net of the platform fee
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeeAccrued {
+    pub referrer: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,692 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hashv, program::invoke, slot_hashes::MAX_ENTRIES, system_instruction::transfer,
+        sysvar::slot_hashes,
+    },
+};
+
+use crate::{
+    constants::{
+        JURY_COMMIT_PERIOD_SECONDS, JURY_REVEAL_PERIOD_SECONDS, JURY_SEED_DELAY_SLOTS, JURY_SIZE,
+        JUROR_REWARD_BPS,
+        JUROR_SLASH_BPS, JUROR_UNBONDING_PERIOD_SECONDS, MAX_JURORS, MIN_JUROR_STAKE,
+    },
+    errors::{ArbitrationError, DisputeError, RewardError, TaskError},
+    events::{JuryRequested, JurySummoned},
+    release_reward,
+    settle_dispute,
+    states::{
+        AdminAccount, DisputeAccount, DisputeStatus, JurorAccount, JurorPool, JurorVote,
+        JuryAccount, ProtocolStats, Responder, ResponseAccount, RewardVault, TaskAccount,
+    },
+};
+
+#[derive(Accounts)]
+pub struct RegisterJuror<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [b"juror_pool"],
+        bump,
+        payer = authority,
+        space = 8 + JurorPool::INIT_SPACE
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    #[account(
+        init,
+        seeds = [b"juror", authority.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + JurorAccount::INIT_SPACE
+    )]
+    pub juror_account: Account<'info, JurorAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterJuror<'info> {
+    pub fn register_juror(&mut self, stake: u64, bumps: RegisterJurorBumps) -> Result<()> {
+        require!(stake >= MIN_JUROR_STAKE, ArbitrationError::InsufficientStake);
+        require!(
+            self.juror_pool.jurors.len() < MAX_JURORS,
+            ArbitrationError::JurorPoolFull
+        );
+
+        invoke(
+            &transfer(&self.authority.key(), &self.juror_account.key(), stake),
+            &[
+                self.authority.to_account_info(),
+                self.juror_account.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+        )
+        .map_err(|e| {
+            msg!("Failed to transfer juror stake ({} lamports): {}", stake, e);
+            RewardError::TransferFailed
+        })?;
+
+        self.juror_account.set_inner(JurorAccount {
+            authority: self.authority.key(),
+            stake,
+            active_cases: 0,
+            unbonding_at: 0,
+            bump: bumps.juror_account,
+        });
+
+        self.juror_pool.bump = bumps.juror_pool;
+        self.juror_pool.jurors.push(self.authority.key());
+        self.juror_pool.active_juries.push(0);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UnregisterJuror<'info> {
+    #[account(
+        mut,
+        seeds = [b"juror_pool"],
+        bump = juror_pool.bump
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"juror", authority.key().as_ref()],
+        bump = juror_account.bump
+    )]
+    pub juror_account: Account<'info, JurorAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UnregisterJuror<'info> {
+    pub fn unregister_juror(&mut self) -> Result<()> {
+        require!(
+            self.juror_account.unbonding_at == 0,
+            ArbitrationError::JurorUnbonding
+        );
+
+        // Empanelled jurors must stay slashable until their juries are settled
+        let pool = &mut self.juror_pool;
+        let authority = self.authority.key();
+        if let Some(index) = pool.jurors.iter().position(|juror| *juror == authority) {
+            require!(
+                pool.active_juries[index] == 0,
+                ArbitrationError::JurorHasActiveCases
+            );
+            pool.jurors.remove(index);
+            pool.active_juries.remove(index);
+        }
+        self.juror_account.unbonding_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawJurorStake<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"juror", authority.key().as_ref()],
+        bump = juror_account.bump,
+        close = authority
+    )]
+    pub juror_account: Account<'info, JurorAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+impl<'info> WithdrawJurorStake<'info> {
+    pub fn withdraw_juror_stake(&mut self) -> Result<()> {
+        let juror = &self.juror_account;
+
+        require!(
+            juror.unbonding_at != 0
+                && Clock::get()?.unix_timestamp
+                    >= juror.unbonding_at.saturating_add(JUROR_UNBONDING_PERIOD_SECONDS),
+            ArbitrationError::UnbondingNotFinished
+        );
+        require!(
+            juror.active_cases == 0,
+            ArbitrationError::JurorHasActiveCases
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RequestJury<'info> {
+    #[account(
+        mut,
+        constraint = dispute_account.status == DisputeStatus::Open @ DisputeError::DisputeNotOpen,
+        constraint = !dispute_account.has_jury @ DisputeError::JuryAssigned
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(address = dispute_account.response)]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    // Escalation blocks admin resolution, so only the arbitrator and the parties may ask for it
+    #[account(
+        constraint = [
+            admin_account.authority,
+            dispute_account.challenger,
+            response_account.responder,
+        ]
+        .contains(&requester.key()) @ TaskError::Unauthorized
+    )]
+    pub requester: Signer<'info>,
+}
+
+impl<'info> RequestJury<'info> {
+    pub fn request_jury(&mut self) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let dispute = &mut self.dispute_account;
+
+        // A request may only be renewed once its seed has fallen out of SlotHashes
+        require!(
+            dispute.jury_seed_slot == 0
+                || slot > dispute.jury_seed_slot.saturating_add(MAX_ENTRIES as u64),
+            ArbitrationError::JuryAlreadyRequested
+        );
+
+        dispute.jury_seed_slot = slot.saturating_add(JURY_SEED_DELAY_SLOTS);
+
+        emit!(JuryRequested {
+            dispute: dispute.key(),
+            seed_slot: dispute.jury_seed_slot,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SummonJury<'info> {
+    #[account(
+        mut,
+        constraint = dispute_account.status == DisputeStatus::Open @ DisputeError::DisputeNotOpen,
+        constraint = !dispute_account.has_jury @ DisputeError::JuryAssigned,
+        constraint = dispute_account.jury_seed_slot != 0 @ ArbitrationError::JuryNotRequested
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(address = dispute_account.response)]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        init,
+        seeds = [b"jury", dispute_account.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + JuryAccount::INIT_SPACE
+    )]
+    pub jury_account: Account<'info, JuryAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"juror_pool"],
+        bump = juror_pool.bump
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    #[account(address = slot_hashes::ID)]
+    /// CHECK: Address is checked, data is read manually
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    // The seed is fixed by the request, so anyone may complete the draw
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SummonJury<'info> {
+    pub fn summon_jury(&mut self, bumps: SummonJuryBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut self.juror_pool;
+
+        // Neither party may sit on the jury deciding their own dispute
+        let parties = [self.dispute_account.challenger, self.response_account.responder];
+        let eligible = pool.jurors.iter().filter(|juror| !parties.contains(juror)).count();
+        require!(eligible >= JURY_SIZE, ArbitrationError::NotEnoughJurors);
+
+        let seed = {
+            let data = self.slot_hashes.try_borrow_data()?;
+            let slot_hash = seed_slot_hash(&data, self.dispute_account.jury_seed_slot)?;
+            hashv(&[&slot_hash, self.dispute_account.key().as_ref()]).to_bytes()
+        };
+
+        let indexes = select_jurors(&pool.jurors, &parties, &seed);
+        let jurors: Vec<Pubkey> = indexes.iter().map(|&index| pool.jurors[index]).collect();
+        for &index in &indexes {
+            pool.active_juries[index] = pool.active_juries[index].saturating_add(1);
+        }
+
+        // Part of the bond is set aside for jurors who vote with the majority
+        let dispute = &mut self.dispute_account;
+        let reward_pool = (dispute.bond as u128 * JUROR_REWARD_BPS as u128 / 10_000) as u64;
+        if reward_pool > 0 {
+            **dispute.to_account_info().try_borrow_mut_lamports()? -= reward_pool;
+            **self.jury_account.to_account_info().try_borrow_mut_lamports()? += reward_pool;
+            dispute.bond -= reward_pool;
+        }
+        dispute.has_jury = true;
+
+        let commit_deadline = now.saturating_add(JURY_COMMIT_PERIOD_SECONDS);
+
+        self.jury_account.set_inner(JuryAccount {
+            dispute: dispute.key(),
+            jurors: jurors.clone(),
+            commit_deadline,
+            reveal_deadline: commit_deadline.saturating_add(JURY_REVEAL_PERIOD_SECONDS),
+            votes_for_challenger: 0,
+            votes_for_responder: 0,
+            reward_pool,
+            is_resolved: false,
+            in_favor_of_challenger: false,
+            settled_mask: 0,
+            bump: bumps.jury_account,
+        });
+
+        emit!(JurySummoned {
+            dispute: dispute.key(),
+            jurors,
+            commit_deadline,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(
+        seeds = [b"jury", jury_account.dispute.as_ref()],
+        bump = jury_account.bump
+    )]
+    pub jury_account: Account<'info, JuryAccount>,
+
+    #[account(
+        init,
+        seeds = [b"juror_vote", jury_account.key().as_ref(), juror.key().as_ref()],
+        bump,
+        payer = juror,
+        space = 8 + JurorVote::INIT_SPACE
+    )]
+    pub juror_vote: Account<'info, JurorVote>,
+
+    #[account(
+        mut,
+        seeds = [b"juror", juror.key().as_ref()],
+        bump = juror_account.bump
+    )]
+    pub juror_account: Account<'info, JurorAccount>,
+
+    #[account(mut)]
+    pub juror: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitVote<'info> {
+    pub fn commit_vote(&mut self, commitment: [u8; 32], bumps: CommitVoteBumps) -> Result<()> {
+        require!(
+            self.jury_account.jurors.contains(&self.juror.key()),
+            ArbitrationError::NotSelectedJuror
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.jury_account.commit_deadline,
+            ArbitrationError::CommitPhaseClosed
+        );
+
+        // Committing locks the stake until this vote is settled
+        self.juror_account.active_cases = self.juror_account.active_cases.saturating_add(1);
+
+        self.juror_vote.set_inner(JurorVote {
+            juror: self.juror.key(),
+            commitment,
+            is_revealed: false,
+            vote_for_challenger: false,
+            bump: bumps.juror_vote,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"jury", jury_account.dispute.as_ref()],
+        bump = jury_account.bump
+    )]
+    pub jury_account: Account<'info, JuryAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"juror_vote", jury_account.key().as_ref(), juror.key().as_ref()],
+        bump = juror_vote.bump
+    )]
+    pub juror_vote: Account<'info, JurorVote>,
+
+    pub juror: Signer<'info>,
+}
+
+impl<'info> RevealVote<'info> {
+    pub fn reveal_vote(&mut self, vote_for_challenger: bool, salt: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let jury = &mut self.jury_account;
+        let vote = &mut self.juror_vote;
+
+        require!(
+            now >= jury.commit_deadline && now < jury.reveal_deadline,
+            ArbitrationError::RevealPhaseNotOpen
+        );
+        require!(!vote.is_revealed, ArbitrationError::RevealPhaseNotOpen);
+        require!(
+            vote_commitment(vote_for_challenger, &salt, &self.juror.key()) == vote.commitment,
+            ArbitrationError::CommitmentMismatch
+        );
+
+        vote.is_revealed = true;
+        vote.vote_for_challenger = vote_for_challenger;
+
+        if vote_for_challenger {
+            jury.votes_for_challenger += 1;
+        } else {
+            jury.votes_for_responder += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveJuryDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"jury", dispute_account.key().as_ref()],
+        bump = jury_account.bump,
+        constraint = !jury_account.is_resolved @ DisputeError::DisputeNotOpen
+    )]
+    pub jury_account: Account<'info, JuryAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", response_account.key().as_ref()],
+        bump = dispute_account.bump,
+        constraint = dispute_account.status == DisputeStatus::Open @ DisputeError::DisputeNotOpen,
+        close = challenger
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(
+        mut,
+        constraint = challenger.key() == dispute_account.challenger @ TaskError::Unauthorized
+    )]
+    pub challenger: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = responder.key() == response_account.responder @ TaskError::Unauthorized
+    )]
+    pub responder: SystemAccount<'info>,
+}
+
+impl<'info> ResolveJuryDispute<'info> {
+    pub fn resolve_jury_dispute(&mut self) -> Result<()> {
+        let jury = &mut self.jury_account;
+        let revealed = jury.votes_for_challenger as usize + jury.votes_for_responder as usize;
+
+        require!(
+            Clock::get()?.unix_timestamp >= jury.reveal_deadline || revealed == jury.jurors.len(),
+            ArbitrationError::VotingNotFinished
+        );
+
+        // Ties keep the verified response in place
+        jury.in_favor_of_challenger = jury.votes_for_challenger > jury.votes_for_responder;
+        jury.is_resolved = true;
+
+        // With nobody to reward, the juror share follows the rest of the bond
+        if jury.majority_votes() == 0 && jury.reward_pool > 0 {
+            **jury.to_account_info().try_borrow_mut_lamports()? -= jury.reward_pool;
+            **self.dispute_account.to_account_info().try_borrow_mut_lamports()? += jury.reward_pool;
+            self.dispute_account.bond += jury.reward_pool;
+            jury.reward_pool = 0;
+        }
+
+        settle_dispute(
+            &mut self.dispute_account,
+            &mut self.response_account,
+            &mut self.responder_account,
+            &mut self.protocol_stats,
+            self.challenger.to_account_info(),
+            self.responder.to_account_info(),
+            self.jury_account.in_favor_of_challenger,
+        )?;
+
+        // An overturned response is no longer owed its reward
+        if self.jury_account.in_favor_of_challenger {
+            release_reward(&mut self.reward_vault, &mut self.response_account);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SettleJuror<'info> {
+    #[account(
+        mut,
+        seeds = [b"jury", jury_account.dispute.as_ref()],
+        bump = jury_account.bump,
+        constraint = jury_account.is_resolved @ ArbitrationError::JuryNotResolved
+    )]
+    pub jury_account: Account<'info, JuryAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"juror", juror.key().as_ref()],
+        bump = juror_account.bump
+    )]
+    pub juror_account: Account<'info, JurorAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"juror_pool"],
+        bump = juror_pool.bump
+    )]
+    pub juror_pool: Account<'info, JurorPool>,
+
+    #[account(
+        seeds = [b"juror_vote", jury_account.key().as_ref(), juror.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Uninitialized when the juror never committed a vote
+    pub juror_vote: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub juror: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    // Receives slashed stake
+    #[account(
+        mut,
+        address = admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin_authority: SystemAccount<'info>,
+}
+
+impl<'info> SettleJuror<'info> {
+    pub fn settle_juror(&mut self) -> Result<()> {
+        let jury = &mut self.jury_account;
+
+        let index = jury
+            .jurors
+            .iter()
+            .position(|juror| *juror == self.juror.key())
+            .ok_or(ArbitrationError::NotSelectedJuror)?;
+        require!(
+            jury.settled_mask & (1 << index) == 0,
+            ArbitrationError::JurorAlreadySettled
+        );
+
+        let committed = !self.juror_vote.data_is_empty();
+        let voted_with_majority = committed && {
+            let data = self.juror_vote.try_borrow_data()?;
+            let vote = JurorVote::try_deserialize(&mut &data[..])?;
+            vote.is_revealed && vote.vote_for_challenger == jury.in_favor_of_challenger
+        };
+
+        if voted_with_majority {
+            let reward = jury.reward_pool / jury.majority_votes() as u64;
+            **jury.to_account_info().try_borrow_mut_lamports()? -= reward;
+            **self.juror.try_borrow_mut_lamports()? += reward;
+        } else {
+            let juror_account = &mut self.juror_account;
+            let slash = (juror_account.stake as u128 * JUROR_SLASH_BPS as u128 / 10_000) as u64;
+            **juror_account.to_account_info().try_borrow_mut_lamports()? -= slash;
+            **self.admin_authority.try_borrow_mut_lamports()? += slash;
+            juror_account.stake -= slash;
+        }
+
+        if committed {
+            self.juror_account.active_cases = self.juror_account.active_cases.saturating_sub(1);
+        }
+        jury.settled_mask |= 1 << index;
+
+        // Jurors cannot leave the pool while empanelled, so they are still in it
+        let pool = &mut self.juror_pool;
+        if let Some(position) = pool.jurors.iter().position(|juror| *juror == self.juror.key()) {
+            pool.active_juries[position] = pool.active_juries[position].saturating_sub(1);
+        }
+
+        // Once every juror is settled, rounding dust left in the reward pool is swept
+        if jury.settled_mask.count_ones() as usize == jury.jurors.len() {
+            let info = jury.to_account_info();
+            let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+            let dust = info.lamports().saturating_sub(rent_exempt);
+            if dust > 0 {
+                **info.try_borrow_mut_lamports()? -= dust;
+                **self.admin_authority.try_borrow_mut_lamports()? += dust;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn vote_commitment(vote_for_challenger: bool, salt: &[u8; 32], juror: &Pubkey) -> [u8; 32] {
+    hashv(&[&[vote_for_challenger as u8], salt, juror.as_ref()]).to_bytes()
+}
+
+// Returns the hash of the first block at or after `seed_slot`. SlotHashes
+// lists (u64 slot, [u8; 32] hash) entries newest first after a u64 count,
+// and only an older entry proves the first block after the seed is still listed.
+fn seed_slot_hash(data: &[u8], seed_slot: u64) -> Result<[u8; 32]> {
+    require!(data.len() >= 8, anchor_lang::error::ErrorCode::AccountDidNotDeserialize);
+    let mut count = [0u8; 8];
+    count.copy_from_slice(&data[..8]);
+    let entries = (u64::from_le_bytes(count) as usize).min((data.len() - 8) / 40);
+
+    let mut candidate = None;
+    for entry in data[8..8 + entries * 40].chunks_exact(40) {
+        let mut slot = [0u8; 8];
+        slot.copy_from_slice(&entry[..8]);
+        if u64::from_le_bytes(slot) < seed_slot {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(candidate.ok_or(ArbitrationError::JurySeedPending)?);
+            return Ok(hash);
+        }
+        candidate = Some(&entry[8..]);
+    }
+
+    err!(ArbitrationError::JurySeedExpired)
+}
+
+// Draws JURY_SIZE distinct pool indexes, skipping the excluded keys. The seed
+// comes from a slot hash fixed before it was known, which is unpredictable
+// enough for juror selection but can be influenced by that slot's leader, so
+// it must not guard anything more valuable.
+fn select_jurors(jurors: &[Pubkey], excluded: &[Pubkey], seed: &[u8; 32]) -> Vec<usize> {
+    let pool_len = jurors.len();
+    let mut selected = Vec::with_capacity(JURY_SIZE);
+    let mut round: u64 = 0;
+
+    while selected.len() < JURY_SIZE {
+        let draw = hashv(&[seed, &round.to_le_bytes()]).to_bytes();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&draw[..8]);
+
+        let mut index = (u64::from_le_bytes(bytes) % pool_len as u64) as usize;
+        while selected.contains(&index) || excluded.contains(&jurors[index]) {
+            index = (index + 1) % pool_len;
+        }
+
+        selected.push(index);
+        round += 1;
+    }
+
+    selected
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_DENYLIST_REASON_LEN,
+    errors::{DenylistError, TaskError},
+    events::{ResponderDenylisted, ResponderRemovedFromDenylist},
+    states::{AdminAccount, DenylistEntry},
+};
+
+#[derive(Accounts)]
+#[instruction(responder: Pubkey)]
+pub struct AddToDenylist<'info> {
+    #[account(
+        init,
+        seeds = [b"denylist", responder.as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + DenylistEntry::INIT_SPACE
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddToDenylist<'info> {
+    pub fn add_to_denylist(
+        &mut self,
+        responder: Pubkey,
+        reason: String,
+        bumps: AddToDenylistBumps,
+    ) -> Result<()> {
+        require!(
+            reason.len() <= MAX_DENYLIST_REASON_LEN,
+            DenylistError::ReasonTooLong
+        );
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        self.denylist_entry.set_inner(DenylistEntry {
+            responder,
+            added_by: self.admin.key(),
+            added_at: timestamp,
+            bump: bumps.denylist_entry,
+            reason: reason.clone(),
+        });
+
+        emit!(ResponderDenylisted {
+            responder,
+            added_by: self.admin.key(),
+            reason,
+            timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RemoveFromDenylist<'info> {
+    #[account(
+        mut,
+        seeds = [b"denylist", denylist_entry.responder.as_ref()],
+        bump = denylist_entry.bump,
+        close = admin
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> RemoveFromDenylist<'info> {
+    pub fn remove_from_denylist(&mut self) -> Result<()> {
+        emit!(ResponderRemovedFromDenylist {
+            responder: self.denylist_entry.responder,
+            removed_by: self.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+// Denylist entries are only ever created by the program, so an empty account at
+// the derived address means the responder is not blocked.
+pub(crate) fn ensure_not_denylisted(denylist_entry: &AccountInfo) -> Result<()> {
+    require!(
+        denylist_entry.data_is_empty(),
+        DenylistError::ResponderDenylisted
+    );
+
+    Ok(())
+}
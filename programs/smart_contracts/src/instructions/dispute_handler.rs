@@ -0,0 +1,267 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction::transfer},
+};
+
+use crate::{
+    constants::MIN_DISPUTE_BOND,
+    errors::{DisputeError, RewardError, TaskError},
+    events::{DisputeOpened, DisputeResolved},
+    release_reward,
+    states::{
+        AdminAccount, DisputeAccount, DisputeStatus, ProtocolStats, Responder, ResponseAccount,
+        RewardVault, TaskAccount,
+    },
+};
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    #[account(
+        init,
+        seeds = [b"dispute", response_account.key().as_ref()],
+        bump,
+        payer = challenger,
+        space = 8 + DisputeAccount::INIT_SPACE
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenDispute<'info> {
+    pub fn open_dispute(&mut self, bond: u64, bumps: OpenDisputeBumps) -> Result<()> {
+        let response = &mut self.response_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(response.is_verified, RewardError::ResponseNotVerified);
+        require!(!response.is_paid, RewardError::RewardAlreadyPaid);
+        // Settled disputes are closed, so the PDA alone no longer stops a second one
+        require!(!response.dispute_settled, DisputeError::DisputeWindowClosed);
+        require!(
+            now <= response.verified_at.saturating_add(self.task_account.dispute_window),
+            DisputeError::DisputeWindowClosed
+        );
+
+        // The task creator may dispute for free, anyone else has to post a bond
+        if self.challenger.key() != self.task_account.creator {
+            require!(bond >= MIN_DISPUTE_BOND, DisputeError::InsufficientBond);
+        }
+
+        if bond > 0 {
+            invoke(
+                &transfer(&self.challenger.key(), &self.dispute_account.key(), bond),
+                &[
+                    self.challenger.to_account_info(),
+                    self.dispute_account.to_account_info(),
+                    self.system_program.to_account_info(),
+                ],
+            )
+            .map_err(|e| {
+                msg!("Failed to transfer dispute bond ({} lamports): {}", bond, e);
+                RewardError::TransferFailed
+            })?;
+        }
+
+        self.dispute_account.set_inner(DisputeAccount {
+            task: self.task_account.key(),
+            response: response.key(),
+            challenger: self.challenger.key(),
+            bond,
+            opened_at: now,
+            resolved_at: 0,
+            status: DisputeStatus::Open,
+            has_jury: false,
+            jury_seed_slot: 0,
+            bump: bumps.dispute_account,
+        });
+
+        response.is_disputed = true;
+        self.responder_account.record_disputed(now);
+
+        emit!(DisputeOpened {
+            dispute: self.dispute_account.key(),
+            response: response.key(),
+            challenger: self.challenger.key(),
+            bond,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute", response_account.key().as_ref()],
+        bump = dispute_account.bump,
+        constraint = dispute_account.status == DisputeStatus::Open @ DisputeError::DisputeNotOpen,
+        constraint = !dispute_account.has_jury @ DisputeError::JuryAssigned,
+        close = challenger
+    )]
+    pub dispute_account: Account<'info, DisputeAccount>,
+
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    #[account(
+        mut,
+        constraint = challenger.key() == dispute_account.challenger @ TaskError::Unauthorized
+    )]
+    pub challenger: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = responder.key() == response_account.responder @ TaskError::Unauthorized
+    )]
+    pub responder: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    // The admin acts as arbitrator
+    #[account(
+        constraint = arbitrator.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub arbitrator: Signer<'info>,
+}
+
+impl<'info> ResolveDispute<'info> {
+    pub fn resolve_dispute(&mut self, in_favor_of_challenger: bool) -> Result<()> {
+        settle_dispute(
+            &mut self.dispute_account,
+            &mut self.response_account,
+            &mut self.responder_account,
+            &mut self.protocol_stats,
+            self.challenger.to_account_info(),
+            self.responder.to_account_info(),
+            in_favor_of_challenger,
+        )?;
+
+        // An overturned response is no longer owed its reward
+        if in_favor_of_challenger {
+            release_reward(&mut self.reward_vault, &mut self.response_account);
+        }
+
+        Ok(())
+    }
+}
+
+// Applies a dispute outcome, shared by admin and jury arbitration. Winning
+// challengers get their bond back, otherwise it compensates the responder.
+// Callers close the dispute account to the challenger afterwards.
+pub(crate) fn settle_dispute<'info>(
+    dispute: &mut Account<'info, DisputeAccount>,
+    response: &mut Account<'info, ResponseAccount>,
+    responder_account: &mut Account<'info, Responder>,
+    protocol_stats: &mut Account<'info, ProtocolStats>,
+    challenger: AccountInfo<'info>,
+    responder: AccountInfo<'info>,
+    in_favor_of_challenger: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let bond_recipient = if in_favor_of_challenger {
+        challenger
+    } else {
+        responder
+    };
+
+    if dispute.bond > 0 {
+        **dispute.to_account_info().try_borrow_mut_lamports()? -= dispute.bond;
+        **bond_recipient.try_borrow_mut_lamports()? += dispute.bond;
+    }
+
+    if in_favor_of_challenger {
+        if response.is_verified {
+            protocol_stats.record_unverified();
+        }
+        response.is_verified = false;
+        response.is_rejected = true;
+        responder_account.record_rejected(false, now);
+        dispute.status = DisputeStatus::ResolvedForChallenger;
+    } else {
+        dispute.status = DisputeStatus::ResolvedForResponder;
+    }
+
+    response.is_disputed = false;
+    response.dispute_settled = true;
+    dispute.resolved_at = now;
+
+    emit!(DisputeResolved {
+        dispute: dispute.key(),
+        response: response.key(),
+        in_favor_of_challenger,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::AdminAccount;
+use crate::{errors::TaskError, AdminAccount, ProtocolStats};
 
 #[derive(Accounts)]
 pub struct InitAdmin<'info> {
@@ -16,6 +16,15 @@ pub struct InitAdmin<'info> {
     )]
     pub admin_account: Account<'info, AdminAccount>,
 
+    #[account(
+      init,
+      payer = admin,
+      space = 8 + ProtocolStats::INIT_SPACE,
+      seeds = [b"protocol_stats"],
+      bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -23,6 +32,40 @@ impl<'info> InitAdmin<'info> {
     pub fn delegate(&mut self, bumps: InitAdminBumps) -> Result<()> {
         self.admin_account.authority = self.admin.key();
         self.admin_account.bump = bumps.admin_account;
+        self.protocol_stats.bump = bumps.protocol_stats;
+        Ok(())
+    }
+}
+
+// Programs whose admin was set up before protocol statistics existed create
+// the stats account separately.
+#[derive(Accounts)]
+pub struct InitProtocolStats<'info> {
+    #[account(
+      seeds = [b"admin"],
+      bump = admin_account.bump,
+      constraint = admin_account.authority == admin.key() @ TaskError::Unauthorized
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+      init,
+      payer = admin,
+      space = 8 + ProtocolStats::INIT_SPACE,
+      seeds = [b"protocol_stats"],
+      bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitProtocolStats<'info> {
+    pub fn init_protocol_stats(&mut self, bumps: InitProtocolStatsBumps) -> Result<()> {
+        self.protocol_stats.bump = bumps.protocol_stats;
         Ok(())
     }
 }
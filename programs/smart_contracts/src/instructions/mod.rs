@@ -2,8 +2,24 @@ pub mod response_handler;
 pub mod reward_vault_handler;
 pub mod task_handlers;
 pub mod init_admin;
+pub mod denylist_handler;
+pub mod dispute_handler;
+pub mod arbitration_handler;
+pub mod template_handler;
+pub mod pipeline_handler;
+pub mod sponsor_handler;
+pub mod tip_handler;
+pub mod referral_handler;
 
 pub use response_handler::*;
 pub use reward_vault_handler::*;
 pub use task_handlers::*;
-pub use init_admin::*;
\ No newline at end of file
+pub use init_admin::*;
+pub use denylist_handler::*;
+pub use dispute_handler::*;
+pub use arbitration_handler::*;
+pub use template_handler::*;
+pub use pipeline_handler::*;
+pub use sponsor_handler::*;
+pub use tip_handler::*;
+pub use referral_handler::*;
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_PREREQUISITES, MAX_PREREQUISITE_OUTPUTS},
+    errors::{DisputeError, PipelineError, TaskError},
+    states::{ResponseAccount, TaskAccount},
+};
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetTaskPrerequisites<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetTaskPrerequisites<'info> {
+    pub fn set_task_prerequisites(
+        &mut self,
+        _task_id: u64,
+        prerequisites: Vec<Pubkey>,
+    ) -> Result<()> {
+        let task_key = self.task_account.key();
+
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(
+            self.task_account.responses_received == 0,
+            TaskError::TaskHasResponses
+        );
+        require!(
+            prerequisites.len() <= MAX_PREREQUISITES,
+            PipelineError::TooManyPrerequisites
+        );
+        for (i, prerequisite) in prerequisites.iter().enumerate() {
+            require!(
+                *prerequisite != task_key && !prerequisites[..i].contains(prerequisite),
+                PipelineError::InvalidPrerequisite
+            );
+        }
+
+        self.task_account.prerequisites = prerequisites;
+        self.task_account.prerequisite_outputs.clear();
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct LinkPrerequisiteOutput<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub prerequisite_task: Account<'info, TaskAccount>,
+
+    #[account(
+        seeds = [
+            b"response",
+            prerequisite_task.key().as_ref(),
+            prerequisite_response.responder.as_ref(),
+            &prerequisite_response.index.to_le_bytes()
+        ],
+        bump = prerequisite_response.bump
+    )]
+    pub prerequisite_response: Account<'info, ResponseAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> LinkPrerequisiteOutput<'info> {
+    // Feeds the CID of an accepted prerequisite response into the dependent
+    // task, so its responders work on the upstream result.
+    pub fn link_prerequisite_output(&mut self, _task_id: u64) -> Result<()> {
+        let task = &mut self.task_account;
+        let response = &self.prerequisite_response;
+
+        require!(!task.is_complete, TaskError::TaskAlreadyComplete);
+        require!(
+            task.prerequisites.contains(&self.prerequisite_task.key()),
+            PipelineError::InvalidPrerequisite
+        );
+        require!(
+            self.prerequisite_task.is_complete,
+            PipelineError::PrerequisiteNotComplete
+        );
+        require!(
+            response.is_verified && !response.is_rejected && !response.is_disputed,
+            PipelineError::OutputNotAccepted
+        );
+        // Only outputs that can no longer be overturned are fed downstream
+        let window_end = response
+            .verified_at
+            .saturating_add(self.prerequisite_task.dispute_window);
+        require!(
+            response.dispute_settled || Clock::get()?.unix_timestamp >= window_end,
+            DisputeError::DisputeWindowOpen
+        );
+
+        let cid = response.cid.ok_or(PipelineError::OutputNotAccepted)?;
+        require!(
+            !task.prerequisite_outputs.contains(&cid),
+            PipelineError::OutputAlreadyLinked
+        );
+        require!(
+            task.prerequisite_outputs.len() < MAX_PREREQUISITE_OUTPUTS,
+            PipelineError::TooManyOutputs
+        );
+
+        task.prerequisite_outputs.push(cid);
+
+        Ok(())
+    }
+}
+
+// Submissions pass the prerequisite tasks, in declaration order, as the
+// leading remaining accounts.
+pub(crate) fn ensure_prerequisites_complete<'info>(
+    task: &TaskAccount,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        remaining_accounts.len() >= task.prerequisites.len(),
+        PipelineError::PrerequisiteMissing
+    );
+
+    for (prerequisite, info) in task.prerequisites.iter().zip(remaining_accounts) {
+        require_keys_eq!(*prerequisite, info.key(), PipelineError::PrerequisiteMissing);
+
+        let prerequisite_task = Account::<TaskAccount>::try_from(info)?;
+        require!(
+            prerequisite_task.is_complete,
+            PipelineError::PrerequisiteNotComplete
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction::transfer},
+};
+
+use crate::{
+    constants::MAX_REFERRAL_SHARE_BPS,
+    errors::{ReferralError, RewardError, TaskError},
+    events::{ReferralFeeAccrued, ReferralFeesClaimed},
+    states::{AdminAccount, CreatorProfile, ReferralConfig, ReferrerAccrual},
+};
+
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"referral_config"],
+        bump,
+        payer = admin,
+        space = 8 + ReferralConfig::INIT_SPACE
+    )]
+    pub referral_config: Account<'info, ReferralConfig>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetReferralShare<'info> {
+    pub fn set_referral_share(
+        &mut self,
+        share_bps: u16,
+        bumps: SetReferralShareBumps,
+    ) -> Result<()> {
+        require!(
+            share_bps <= MAX_REFERRAL_SHARE_BPS,
+            ReferralError::InvalidReferralShare
+        );
+
+        self.referral_config.set_inner(ReferralConfig {
+            share_bps,
+            bump: bumps.referral_config,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        seeds = [b"referrer", referrer.key().as_ref()],
+        bump,
+        payer = referrer,
+        space = 8 + ReferrerAccrual::INIT_SPACE
+    )]
+    pub referrer_accrual: Account<'info, ReferrerAccrual>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    pub fn register_referrer(&mut self, bumps: RegisterReferrerBumps) -> Result<()> {
+        self.referrer_accrual.set_inner(ReferrerAccrual {
+            referrer: self.referrer.key(),
+            unclaimed: 0,
+            total_accrued: 0,
+            bump: bumps.referrer_accrual,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetReferrer<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    // Referrers must be registered so deposits always have somewhere to route their share
+    #[account(
+        seeds = [b"referrer", referrer_accrual.referrer.as_ref()],
+        bump = referrer_accrual.bump
+    )]
+    pub referrer_accrual: Account<'info, ReferrerAccrual>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetReferrer<'info> {
+    pub fn set_referrer(&mut self, bumps: SetReferrerBumps) -> Result<()> {
+        let referrer = self.referrer_accrual.referrer;
+        require!(
+            referrer != self.creator.key(),
+            ReferralError::SelfReferral
+        );
+
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
+        }
+
+        // The referrer is fixed once set so creators cannot redirect the share
+        require!(
+            self.creator_profile.referrer.is_none(),
+            ReferralError::ReferrerAlreadySet
+        );
+        self.creator_profile.referrer = Some(referrer);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.key().as_ref()],
+        bump = referrer_accrual.bump,
+        has_one = referrer
+    )]
+    pub referrer_accrual: Account<'info, ReferrerAccrual>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+}
+
+impl<'info> ClaimReferralFees<'info> {
+    pub fn claim_referral_fees(&mut self) -> Result<()> {
+        let amount = self.referrer_accrual.unclaimed;
+        require!(amount > 0, ReferralError::NothingToClaim);
+
+        **self
+            .referrer_accrual
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **self.referrer.try_borrow_mut_lamports()? += amount;
+
+        self.referrer_accrual.unclaimed = 0;
+
+        emit!(ReferralFeesClaimed {
+            referrer: self.referrer.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Claimed {} lamports of referral fees", amount);
+
+        Ok(())
+    }
+}
+
+// The config is created by the first set_referral_share, so an empty account
+// means referrers get nothing. Only this program can allocate data at its PDA.
+fn referral_share_bps(referral_config: &AccountInfo) -> Result<u16> {
+    if referral_config.data_is_empty() {
+        return Ok(0);
+    }
+
+    let config = ReferralConfig::try_deserialize(&mut &referral_config.try_borrow_data()?[..])?;
+    Ok(config.share_bps)
+}
+
+// Pays a referred creator's referrer their share of the platform fee on a
+// creator deposit. Returns the share, which the caller deducts from the fee
+// paid to the admin.
+pub(crate) fn referral_fee<'info>(
+    payer: &Signer<'info>,
+    referrer: Option<Pubkey>,
+    accrual: Option<&mut Account<'info, ReferrerAccrual>>,
+    referral_config: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    platform_fee: u64,
+) -> Result<u64> {
+    let Some(referrer) = referrer else {
+        return Ok(0);
+    };
+
+    let accrual = accrual
+        .filter(|accrual| accrual.referrer == referrer)
+        .ok_or(ReferralError::ReferrerAccountMismatch)?;
+    let share_bps = referral_share_bps(referral_config)?;
+
+    accrue_referral_fee(payer, accrual, system_program, platform_fee, share_bps)
+}
+
+// Sends the referrer's share of a platform fee from the payer to the
+// referrer's accrual account.
+fn accrue_referral_fee<'info>(
+    payer: &Signer<'info>,
+    accrual: &mut Account<'info, ReferrerAccrual>,
+    system_program: &Program<'info, System>,
+    platform_fee: u64,
+    share_bps: u16,
+) -> Result<u64> {
+    let share = (platform_fee as u128 * share_bps as u128 / 10_000) as u64;
+    if share == 0 {
+        return Ok(0);
+    }
+
+    invoke(
+        &transfer(&payer.key(), &accrual.key(), share),
+        &[
+            payer.to_account_info(),
+            accrual.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )
+    .map_err(|e| {
+        msg!(
+            "Failed to transfer referral fee ({} lamports) to referrer {}: {}",
+            share,
+            accrual.referrer,
+            e
+        );
+        RewardError::TransferFailed
+    })?;
+
+    accrual.unclaimed = accrual
+        .unclaimed
+        .checked_add(share)
+        .ok_or(RewardError::TransferFailed)?;
+    accrual.total_accrued = accrual.total_accrued.saturating_add(share);
+
+    emit!(ReferralFeeAccrued {
+        referrer: accrual.referrer,
+        creator: payer.key(),
+        amount: share,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(share)
+}
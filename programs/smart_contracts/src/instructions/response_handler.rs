@@ -1,9 +1,20 @@
 use crate::{
-    errors::TaskError,
-    states::{ResponseAccount, TaskAccount},
+    cid::Cid,
+    constants::MAX_PAYLOAD_SIZE,
+    ensure_fully_funded,
+    ensure_not_denylisted,
+    ensure_prerequisites_complete,
+    errors::{ErrorCode, RewardError, TaskError},
+    lock_reward,
+    merkle::{allowlist_leaf, verify_merkle_proof},
+    release_reward,
+    states::{
+        AggregationMethod, ProtocolStats, RejectionReason, Responder, ResponderTaskState,
+        ResponseAccount, RewardVault, TaskAccount,
+    },
     AdminAccount,
 };
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
 
 #[derive(Accounts)]
 pub struct SubmitResponse<'info> {
@@ -14,19 +25,56 @@ pub struct SubmitResponse<'info> {
     )]
     pub task_account: Account<'info, TaskAccount>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"responder_task", task_account.key().as_ref(), responder.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + ResponderTaskState::INIT_SPACE
+    )]
+    pub responder_task_state: Account<'info, ResponderTaskState>,
+
     #[account(
         init,
-        seeds = [b"response", task_account.key().as_ref(), responder.key().as_ref()],
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            responder.key().as_ref(),
+            &responder_task_state.submitted.to_le_bytes()
+        ],
         bump,
         payer = admin,
-        space = ResponseAccount::INIT_SPACE
+        space = 8 + ResponseAccount::INIT_SPACE
     )]
     pub response_account: Account<'info, ResponseAccount>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"responder", responder.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + Responder::INIT_SPACE
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
     #[account(mut)]
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub responder: AccountInfo<'info>,
 
+    #[account(
+        seeds = [b"denylist", responder.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Must be uninitialized, checked by ensure_not_denylisted
+    pub denylist_entry: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = admin.key() == admin_account.authority
@@ -40,13 +88,65 @@ pub struct SubmitResponse<'info> {
     )]
     pub admin_account: Account<'info, AdminAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SubmitResponse<'info> {
-    pub fn submit_response(&mut self, cid: String) -> Result<()> {
+    pub fn submit_response(
+        &mut self,
+        cid: String,
+        proof: Vec<[u8; 32]>,
+        value: Option<i64>,
+        bumps: SubmitResponseBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
         require!(
-            Clock::get()?.unix_timestamp < self.task_account.deadline,
+            self.task_account.reveal_window == 0,
+            TaskError::CommitRevealRequired
+        );
+
+        let cid = Cid::parse(&cid)?;
+
+        self.record_response(Some(cid), None, value, proof, bumps, remaining_accounts)
+    }
+
+    pub fn commit_response(
+        &mut self,
+        commitment: [u8; 32],
+        proof: Vec<[u8; 32]>,
+        bumps: SubmitResponseBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            self.task_account.reveal_window != 0,
+            TaskError::CommitRevealNotEnabled
+        );
+
+        self.record_response(None, Some(commitment), None, proof, bumps, remaining_accounts)
+    }
+
+    fn record_response(
+        &mut self,
+        cid: Option<Cid>,
+        commitment: Option<[u8; 32]>,
+        numeric_value: Option<i64>,
+        proof: Vec<[u8; 32]>,
+        bumps: SubmitResponseBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(!self.task_account.is_cancelled, TaskError::TaskCancelled);
+
+        require!(
+            now < self.task_account.deadline,
             TaskError::DeadlinePassed
         );
 
@@ -55,24 +155,207 @@ impl<'info> SubmitResponse<'info> {
             TaskError::MaxResponsesReached
         );
 
+        // Submissions only open once every response slot is escrowed
+        ensure_fully_funded(&self.task_account, self.reward_vault.balance)?;
+
+        ensure_not_denylisted(&self.denylist_entry)?;
+
+        ensure_prerequisites_complete(&self.task_account, remaining_accounts)?;
+
+        let index = self.responder_task_state.submitted;
+        require!(
+            index < self.task_account.max_responses_per_responder,
+            TaskError::ResponderLimitReached
+        );
+
+        require!(
+            self.task_account.aggregation == AggregationMethod::None || numeric_value.is_some(),
+            TaskError::MissingNumericValue
+        );
+
+        if let Some(root) = self.task_account.allowlist_root {
+            require!(
+                verify_merkle_proof(&proof, &root, allowlist_leaf(self.responder.key().as_ref())),
+                TaskError::NotAllowlisted
+            );
+        }
+
+        if self.responder_account.authority == Pubkey::default() {
+            self.responder_account
+                .initialize(self.responder.key(), bumps.responder_account);
+        }
+
+        require!(
+            self.responder_account.trust_score >= self.task_account.min_trust_score,
+            TaskError::TrustScoreTooLow
+        );
+
         self.response_account.set_inner(ResponseAccount {
             task_bump: self.task_account.task_bump,
             responder: self.responder.key(),
+            index,
             cid,
-            timestamp: Clock::get()?.unix_timestamp,
-            is_verified: true,
-            bump: self.response_account.bump,
+            timestamp: now,
+            is_verified: false,
+            is_rejected: false,
+            is_disputed: false,
+            dispute_settled: false,
+            is_paid: false,
+            owed: self.task_account.reward_per_response,
+            tipped: 0,
+            verified_at: 0,
+            commitment,
+            is_revealed: false,
+            numeric_value,
+            is_outlier: false,
+            is_aggregated: false,
+            revision: 0,
+            cid_history: [0; 32],
+            payload: Vec::new(),
+            bump: bumps.response_account,
+        });
+        lock_reward(&mut self.reward_vault, self.task_account.reward_per_response)?;
+
+        self.responder_task_state.set_inner(ResponderTaskState {
+            task: self.task_account.key(),
+            responder: self.responder.key(),
+            submitted: index + 1,
+            bump: bumps.responder_task_state,
         });
+        self.responder_account.record_submission(now);
 
         self.task_account.responses_received = self
             .task_account
             .responses_received
             .checked_add(1)
-            .ok_or_else(|| TaskError::MaxResponsesReached)?;
+            .ok_or(TaskError::MaxResponsesReached)?;
+        self.protocol_stats.record_response();
 
         if self.task_account.responses_received == self.task_account.max_responses {
             self.task_account.is_complete = true;
+            self.protocol_stats.record_task_closed();
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AmendResponse<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+}
+
+impl<'info> AmendResponse<'info> {
+    pub fn amend_response(&mut self, cid: String) -> Result<()> {
+        let response = &mut self.response_account;
+
+        require!(
+            Clock::get()?.unix_timestamp < self.task_account.deadline,
+            TaskError::DeadlinePassed
+        );
+        // Committed responses are bound to their commitment
+        require!(
+            response.commitment.is_none(),
+            TaskError::CommitRevealRequired
+        );
+        require!(!response.is_verified, TaskError::ResponseAlreadyVerified);
+        require!(!response.is_rejected, TaskError::ResponseAlreadyRejected);
+
+        let cid = Cid::parse(&cid)?;
+
+        if let Some(previous) = response.cid.replace(cid) {
+            response.cid_history = hashv(&[&response.cid_history, &previous.digest]).to_bytes();
         }
+        response.revision = response.revision.saturating_add(1);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(payload: Vec<u8>)]
+pub struct AttachResponsePayload<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump,
+        realloc = 8 + ResponseAccount::INIT_SPACE + payload.len(),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AttachResponsePayload<'info> {
+    pub fn attach_response_payload(&mut self, payload: Vec<u8>) -> Result<()> {
+        let response = &mut self.response_account;
+
+        require!(payload.len() <= MAX_PAYLOAD_SIZE, ErrorCode::InputTooLarge);
+        require!(
+            Clock::get()?.unix_timestamp < self.task_account.deadline,
+            TaskError::DeadlinePassed
+        );
+        // An inline payload would expose a committed answer before the reveal
+        require!(
+            self.task_account.reveal_window == 0,
+            TaskError::CommitRevealRequired
+        );
+        require!(!response.is_verified, TaskError::ResponseAlreadyVerified);
+        require!(!response.is_rejected, TaskError::ResponseAlreadyRejected);
+
+        response.payload = payload;
 
         Ok(())
     }
@@ -80,9 +363,38 @@ impl<'info> SubmitResponse<'info> {
 
 #[derive(Accounts)]
 pub struct VerifyResponse<'info> {
-    #[account(mut)]
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
     pub response_account: Account<'info, ResponseAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
     #[account(
         mut,
         seeds = [b"admin"],
@@ -90,11 +402,18 @@ pub struct VerifyResponse<'info> {
     )]
     pub admin_account: Account<'info, AdminAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     pub signer: Signer<'info>,
 }
 
 impl<'info> VerifyResponse<'info> {
-    pub fn verify_response(&mut self) -> Result<()> {
+    pub fn verify_response(&mut self, reward_amount: Option<u64>) -> Result<()> {
         let response = &mut self.response_account;
 
         require!(
@@ -102,8 +421,224 @@ impl<'info> VerifyResponse<'info> {
             TaskError::Unauthorized
         );
 
-        response.is_verified = true;
+        require!(!response.is_rejected, TaskError::ResponseAlreadyRejected);
+        require!(
+            response.commitment.is_none() || response.is_revealed,
+            TaskError::ResponseNotRevealed
+        );
+
+        // Partial credit defaults to the full reward and can never exceed it
+        let reward_per_response = self.task_account.reward_per_response;
+        let reward_amount = reward_amount.unwrap_or(reward_per_response);
+        require!(
+            reward_amount <= reward_per_response,
+            RewardError::ExcessiveRewardAmount
+        );
+
+        // Verifying twice is a no-op so the trust score is only credited once
+        if !response.is_verified {
+            let now = Clock::get()?.unix_timestamp;
+
+            // Re-lock at the graded amount, returning the difference to the refundable balance
+            release_reward(&mut self.reward_vault, response);
+            lock_reward(&mut self.reward_vault, reward_amount)?;
+            response.owed = reward_amount;
+
+            response.is_verified = true;
+            response.verified_at = now;
+            self.responder_account.record_accepted(now);
+            self.protocol_stats.record_verified();
+        }
 
         Ok(())
     }
 }
+
+#[derive(Accounts)]
+pub struct RejectResponse<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        constraint = signer.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub signer: Signer<'info>,
+}
+
+impl<'info> RejectResponse<'info> {
+    pub fn reject_response(&mut self, reason: RejectionReason) -> Result<()> {
+        let response = &mut self.response_account;
+
+        require!(!response.is_verified, TaskError::ResponseAlreadyVerified);
+        require!(!response.is_rejected, TaskError::ResponseAlreadyRejected);
+
+        response.is_rejected = true;
+        release_reward(&mut self.reward_vault, response);
+        self.responder_account.record_rejected(
+            reason == RejectionReason::Late,
+            Clock::get()?.unix_timestamp,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RevealResponse<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+}
+
+impl<'info> RevealResponse<'info> {
+    pub fn reveal_response(&mut self, cid: String, salt: [u8; 32]) -> Result<()> {
+        let task = &self.task_account;
+        let response = &mut self.response_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        let commitment = response
+            .commitment
+            .ok_or(TaskError::CommitRevealNotEnabled)?;
+
+        require!(
+            now >= task.deadline && now < task.deadline.saturating_add(task.reveal_window),
+            TaskError::RevealWindowNotOpen
+        );
+        require!(!response.is_revealed, TaskError::RevealWindowNotOpen);
+        require!(
+            response_commitment(&cid, &salt, &response.responder) == commitment,
+            TaskError::ResponseCommitmentMismatch
+        );
+
+        response.cid = Some(Cid::parse(&cid)?);
+        response.is_revealed = true;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ForfeitUnrevealedResponse<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            response_account.responder.as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"responder", response_account.responder.as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+}
+
+impl<'info> ForfeitUnrevealedResponse<'info> {
+    // Permissionless: once the reveal window closes, unrevealed commitments are
+    // rejected so they can never be verified or paid.
+    pub fn forfeit_unrevealed_response(&mut self) -> Result<()> {
+        let task = &self.task_account;
+        let response = &mut self.response_account;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            response.commitment.is_some(),
+            TaskError::CommitRevealNotEnabled
+        );
+        require!(!response.is_revealed, TaskError::ResponseRevealed);
+        require!(!response.is_rejected, TaskError::ResponseAlreadyRejected);
+        require!(
+            now >= task.deadline.saturating_add(task.reveal_window),
+            TaskError::RevealWindowNotOpen
+        );
+
+        response.is_rejected = true;
+        release_reward(&mut self.reward_vault, response);
+        self.responder_account.record_rejected(false, now);
+
+        Ok(())
+    }
+}
+
+pub fn response_commitment(cid: &str, salt: &[u8; 32], responder: &Pubkey) -> [u8; 32] {
+    hashv(&[cid.as_bytes(), salt, responder.as_ref()]).to_bytes()
+}
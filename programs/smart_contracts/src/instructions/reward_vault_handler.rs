@@ -1,6 +1,17 @@
 use crate::{
-    errors::{RewardError, TaskError},
-    states::{RewardVault, TaskAccount, AdminAccount, ResponseAccount}
+    constants::{
+        BASE_REWARD_MULTIPLIER_BPS, MAX_REWARD_MULTIPLIER_BPS, PLATFORM_FEE_DENOMINATOR,
+        PLATFORM_FEE_NUMERATOR, TRUST_SCORE_INITIAL, TRUST_SCORE_MAX,
+    },
+    ensure_not_denylisted,
+    errors::{DisputeError, RewardError, TaskError},
+    referral_fee,
+    release_surplus, settle_vault,
+    events::VaultReconciled,
+    states::{
+        AdminAccount, CreatorProfile, ProtocolStats, ReferrerAccrual, Responder, ResponseAccount,
+        RewardVault, TaskAccount, TaskTemplate,
+    },
 };
 use anchor_lang::{
     prelude::*,
@@ -26,6 +37,30 @@ pub struct DepositFunds<'info> {
     )]
     pub reward_vault: Account<'info, RewardVault>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    // Required when the creator was referred, receives part of the platform fee
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_accrual.referrer.as_ref()],
+        bump = referrer_accrual.bump
+    )]
+    pub referrer_accrual: Option<Account<'info, ReferrerAccrual>>,
+
+    #[account(
+        seeds = [b"referral_config"],
+        bump
+    )]
+    /// CHECK: May be uninitialized, read by referral_fee
+    pub referral_config: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"admin"],
@@ -40,6 +75,13 @@ pub struct DepositFunds<'info> {
     /// CHECK: This is safe because we check it matches admin_account.authority
     pub admin_authority: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     #[account(mut)]
     pub creator: Signer<'info>,
 
@@ -53,82 +95,399 @@ impl<'info> DepositFunds<'info> {
         amount: u64,
         bumps: DepositFundsBumps,
     ) -> Result<()> {
-        if amount == 0 {
-            msg!("Attempted to deposit zero lamports");
-            return Err(RewardError::InvalidDepositAmount.into());
-        }
+        require!(!self.task_account.is_cancelled, TaskError::TaskCancelled);
 
-        let platform_fee = amount
-            .checked_mul(69)
-            .and_then(|v| v.checked_div(1000))
+        let net_deposit = self.collect_deposit(amount, bumps.creator_profile)?;
+
+        let vault_balance = self
+            .reward_vault
+            .balance
+            .checked_add(net_deposit)
             .ok_or_else(|| {
-                msg!("Overflow while calculating platform fee");
+                msg!("Overflow when adding to reward vault balance");
                 RewardError::TransferFailed
             })?;
 
-        let net_deposit = amount.checked_sub(platform_fee).ok_or_else(|| {
-            msg!("Underflow when subtracting platform fee");
-            RewardError::TransferFailed
-        })?;
+        self.reward_vault.task_bump = bumps.task_account;
+        self.reward_vault.balance = vault_balance;
+        self.reward_vault.deposited = self
+            .reward_vault
+            .deposited
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+        self.reward_vault.bump = bumps.reward_vault;
 
-        invoke(
-            &transfer(
-                &self.creator.key(),
-                &self.admin_authority.key(),
-                platform_fee,
-            ),
-            &[
-                self.creator.to_account_info(),
-                self.admin_authority.to_account_info(),
-                self.system_program.to_account_info(),
-            ],
-        )
-        .map_err(|e| {
-            msg!(
-                "Failed to transfer platform fee ({} lamports) to admin: {}",
-                platform_fee,
-                e
-            );
-            RewardError::TransferFailed
-        })?;
+        let shortfall = funding_shortfall(&self.task_account, vault_balance)?;
+        if shortfall > 0 {
+            msg!("Task needs {} more lamports of escrow before it opens", shortfall);
+        }
 
-        // Transfer net deposit to reward vault
-        invoke(
-            &transfer(&self.creator.key(), &self.reward_vault.key(), net_deposit),
-            &[
-                self.creator.to_account_info(),
-                self.reward_vault.to_account_info(),
-                self.system_program.to_account_info(),
-            ],
-        )
-        .map_err(|e| {
-            msg!(
-                "Failed to transfer net deposit ({} lamports) to reward vault: {}",
-                net_deposit,
-                e
-            );
-            RewardError::TransferFailed
-        })?;
+        Ok(())
+    }
 
-        let vault_balance = self
+    pub fn fund_bonus_pool(
+        &mut self,
+        _task_id: u64,
+        amount: u64,
+        max_multiplier_bps: u16,
+        bumps: DepositFundsBumps,
+    ) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(!self.task_account.is_cancelled, TaskError::TaskCancelled);
+        require!(
+            (BASE_REWARD_MULTIPLIER_BPS..=MAX_REWARD_MULTIPLIER_BPS).contains(&max_multiplier_bps),
+            RewardError::InvalidMultiplier
+        );
+
+        let net_deposit = self.collect_deposit(amount, bumps.creator_profile)?;
+
+        let bonus_balance = self
             .reward_vault
-            .balance
+            .bonus_balance
             .checked_add(net_deposit)
             .ok_or_else(|| {
-                msg!("Overflow when adding to reward vault balance");
+                msg!("Overflow when adding to reward vault bonus pool");
                 RewardError::TransferFailed
             })?;
 
-        self.reward_vault.set_inner(RewardVault {
-            task_bump: bumps.task_account,
-            balance: vault_balance,
-            bump: bumps.reward_vault,
+        self.reward_vault.task_bump = bumps.task_account;
+        self.reward_vault.bonus_balance = bonus_balance;
+        self.reward_vault.bump = bumps.reward_vault;
+
+        self.task_account.max_reward_multiplier_bps = max_multiplier_bps;
+
+        Ok(())
+    }
+
+    fn collect_deposit(&mut self, amount: u64, profile_bump: u8) -> Result<u64> {
+        let platform_fee = calculate_platform_fee(amount)?;
+
+        // A referred creator's fee is split before the admin receives the rest
+        let referral_fee = referral_fee(
+            &self.creator,
+            self.creator_profile.referrer,
+            self.referrer_accrual.as_mut(),
+            &self.referral_config,
+            &self.system_program,
+            platform_fee,
+        )?;
+
+        let net_deposit = collect_with_fee(
+            &self.creator,
+            &self.admin_authority,
+            &self.reward_vault.to_account_info(),
+            &self.system_program,
+            amount
+                .checked_sub(referral_fee)
+                .ok_or(RewardError::TransferFailed)?,
+            platform_fee
+                .checked_sub(referral_fee)
+                .ok_or(RewardError::TransferFailed)?,
+        )?;
+
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), profile_bump);
+        }
+        self.creator_profile
+            .record_deposit(net_deposit, amount - net_deposit);
+        self.protocol_stats
+            .record_deposit(net_deposit, amount - net_deposit);
+
+        Ok(net_deposit)
+    }
+}
+
+// Moves `amount` out of the payer's wallet, sending the platform fee to
+// the admin and the rest to the vault. Returns the net deposit.
+pub(crate) fn collect_deposit<'info>(
+    payer: &Signer<'info>,
+    admin_authority: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<u64> {
+    let platform_fee = calculate_platform_fee(amount)?;
+    collect_with_fee(payer, admin_authority, vault, system_program, amount, platform_fee)
+}
+
+// Like collect_deposit, with the fee already calculated by the caller
+pub(crate) fn collect_with_fee<'info>(
+    payer: &Signer<'info>,
+    admin_authority: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+    platform_fee: u64,
+) -> Result<u64> {
+    if amount == 0 {
+        msg!("Attempted to deposit zero lamports");
+        return Err(RewardError::InvalidDepositAmount.into());
+    }
+
+    let net_deposit = amount.checked_sub(platform_fee).ok_or_else(|| {
+        msg!("Underflow when subtracting platform fee");
+        RewardError::TransferFailed
+    })?;
+
+    invoke(
+        &transfer(&payer.key(), &admin_authority.key(), platform_fee),
+        &[
+            payer.to_account_info(),
+            admin_authority.clone(),
+            system_program.to_account_info(),
+        ],
+    )
+    .map_err(|e| {
+        msg!(
+            "Failed to transfer platform fee ({} lamports) to admin: {}",
+            platform_fee,
+            e
+        );
+        RewardError::TransferFailed
+    })?;
+
+    // Transfer net deposit to the vault
+    invoke(
+        &transfer(&payer.key(), &vault.key(), net_deposit),
+        &[
+            payer.to_account_info(),
+            vault.clone(),
+            system_program.to_account_info(),
+        ],
+    )
+    .map_err(|e| {
+        msg!(
+            "Failed to transfer net deposit ({} lamports) to vault: {}",
+            net_deposit,
+            e
+        );
+        RewardError::TransferFailed
+    })?;
+
+    Ok(net_deposit)
+}
+
+pub(crate) fn calculate_platform_fee(amount: u64) -> Result<u64> {
+    let platform_fee = amount
+        .checked_mul(PLATFORM_FEE_NUMERATOR)
+        .and_then(|v| v.checked_div(PLATFORM_FEE_DENOMINATOR))
+        .ok_or_else(|| {
+            msg!("Overflow while calculating platform fee");
+            RewardError::TransferFailed
+        })?;
+
+    Ok(platform_fee)
+}
+
+// Escrow the vault is still missing to cover every unpaid response
+pub(crate) fn funding_shortfall(task: &TaskAccount, vault_balance: u64) -> Result<u64> {
+    let outstanding = task
+        .outstanding_rewards()
+        .ok_or(RewardError::TransferFailed)?;
+
+    Ok(outstanding.saturating_sub(vault_balance))
+}
+
+// Solvency invariant: a task's vault always covers the base reward of every
+// response it can still pay out.
+pub(crate) fn ensure_fully_funded(task: &TaskAccount, vault_balance: u64) -> Result<()> {
+    require!(
+        funding_shortfall(task, vault_balance)? == 0,
+        RewardError::InsufficientVaultBalance
+    );
+
+    Ok(())
+}
+
+// Once responses lock their rewards the vault must cover every lock plus the
+// full reward of each slot still open for submission.
+pub(crate) fn ensure_locks_covered(task: &TaskAccount, vault: &RewardVault) -> Result<()> {
+    let required = task
+        .open_slot_rewards()
+        .and_then(|open| open.checked_add(vault.locked_balance))
+        .ok_or(RewardError::TransferFailed)?;
+    require!(
+        vault.balance >= required,
+        RewardError::InsufficientVaultBalance
+    );
+
+    Ok(())
+}
+
+// Vaults are created by the first deposit, so an empty account escrows nothing.
+// Only this program can allocate data at its own vault PDA.
+pub(crate) fn escrowed_balance(reward_vault: &AccountInfo) -> Result<u64> {
+    if reward_vault.data_is_empty() {
+        return Ok(0);
+    }
+
+    let vault = RewardVault::try_deserialize(&mut &reward_vault.try_borrow_data()?[..])?;
+    Ok(vault.balance)
+}
+
+// Epoch tasks are created by their template, whose creator owns the profile.
+// Only this program can allocate data at a template PDA.
+pub(crate) fn profile_owner(task_creator: &AccountInfo) -> Result<Pubkey> {
+    if task_creator.owner == &crate::ID && !task_creator.data_is_empty() {
+        let template = TaskTemplate::try_deserialize(&mut &task_creator.try_borrow_data()?[..])?;
+        return Ok(template.creator);
+    }
+
+    Ok(task_creator.key())
+}
+
+// Profiles are created by the creator's first deposit or refund, so an empty
+// account has nothing to update. Once a profile exists it cannot be skipped.
+pub(crate) fn update_creator_profile(
+    creator_profile: &AccountInfo,
+    owner: &Pubkey,
+    update: impl FnOnce(&mut CreatorProfile),
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[b"creator", owner.as_ref()], &crate::ID);
+    require_keys_eq!(
+        creator_profile.key(),
+        expected,
+        RewardError::InvalidCreatorProfile
+    );
+
+    if creator_profile.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut data = creator_profile.try_borrow_mut_data()?;
+    let mut profile = CreatorProfile::try_deserialize(&mut &data[..])?;
+    update(&mut profile);
+    profile.try_serialize(&mut &mut data[..])
+}
+
+// Smallest deposit that leaves `net_deposit` in the vault after the platform
+// fee. The fee rounds down, so the net of `gross` is ceil(gross * kept / denominator).
+pub(crate) fn gross_deposit(net_deposit: u64) -> Result<u64> {
+    if net_deposit == 0 {
+        return Ok(0);
+    }
+
+    let kept = PLATFORM_FEE_DENOMINATOR - PLATFORM_FEE_NUMERATOR;
+    let gross = u128::from(net_deposit - 1) * u128::from(PLATFORM_FEE_DENOMINATOR)
+        / u128::from(kept)
+        + 1;
+
+    u64::try_from(gross).map_err(|_| RewardError::TransferFailed.into())
+}
+
+// Reserves a submitted response's reward so refunds can never reach it
+pub(crate) fn lock_reward(vault: &mut RewardVault, amount: u64) -> Result<()> {
+    let locked = vault
+        .locked_balance
+        .checked_add(amount)
+        .ok_or(RewardError::TransferFailed)?;
+    require!(locked <= vault.balance, RewardError::InsufficientVaultBalance);
+
+    vault.locked_balance = locked;
+    Ok(())
+}
+
+// Returns a response's reserved reward to the refundable balance
+pub(crate) fn release_reward(vault: &mut RewardVault, response: &mut ResponseAccount) {
+    vault.locked_balance = vault.locked_balance.saturating_sub(response.owed);
+    response.owed = 0;
+}
+
+// Lamports held above rent and the tracked balances, such as direct transfers
+// to the vault. Fails if the vault holds less than it tracks.
+pub(crate) fn unsolicited_lamports(vault: &Account<RewardVault>) -> Result<u64> {
+    let info = vault.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(info.data_len());
+    let spendable = info.lamports().saturating_sub(rent_exempt);
+
+    let tracked = vault
+        .balance
+        .checked_add(vault.bonus_balance)
+        .and_then(|v| v.checked_add(vault.sponsor_pool))
+        .ok_or(RewardError::VaultBalanceMismatch)?;
+
+    spendable
+        .checked_sub(tracked)
+        .ok_or_else(|| {
+            msg!("Vault tracks {} lamports but only {} are spendable", tracked, spendable);
+            RewardError::VaultBalanceMismatch.into()
+        })
+}
+
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", escrow_owner.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    /// CHECK: Task or template the vault escrows for, only used as a seed
+    pub escrow_owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin.key() == admin_account.authority @ TaskError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+}
+
+impl<'info> ReconcileVault<'info> {
+    // Sweeps unsolicited lamports to the admin, leaving the vault holding
+    // exactly rent plus its tracked balances.
+    pub fn reconcile_vault(&mut self) -> Result<()> {
+        let swept = unsolicited_lamports(&self.reward_vault)?;
+
+        if swept > 0 {
+            **self
+                .reward_vault
+                .to_account_info()
+                .try_borrow_mut_lamports()? -= swept;
+            **self.admin.try_borrow_mut_lamports()? += swept;
+        }
+
+        emit!(VaultReconciled {
+            vault: self.reward_vault.key(),
+            tracked_balance: self.reward_vault.balance,
+            swept,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 }
 
+#[derive(Accounts)]
+pub struct RequiredFunding<'info> {
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Empty until the task is funded, read by escrowed_balance
+    pub reward_vault: UncheckedAccount<'info>,
+}
+
+impl<'info> RequiredFunding<'info> {
+    // Gross deposit, platform fee included, that opens the task for submissions
+    pub fn required_funding(&self) -> Result<u64> {
+        let balance = escrowed_balance(&self.reward_vault)?;
+        gross_deposit(funding_shortfall(&self.task_account, balance)?)
+    }
+}
+
 #[derive(Accounts)]
 pub struct RefundRemaining<'info> {
     #[account(
@@ -142,17 +501,34 @@ pub struct RefundRemaining<'info> {
     #[account(
         mut,
         seeds = [b"vault", task_account.key().as_ref()],
-        bump = reward_vault.bump,
-        close = creator
+        bump = reward_vault.bump
     )]
     pub reward_vault: Account<'info, RewardVault>,
 
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     #[account(mut)]
     pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> RefundRemaining<'info> {
-    pub fn refund_remaining(&mut self) -> Result<()> {
+    pub fn refund_remaining(&mut self, bumps: RefundRemainingBumps) -> Result<()> {
         require!(
             !self.task_account.is_complete,
             TaskError::TaskAlreadyComplete
@@ -161,13 +537,117 @@ impl<'info> RefundRemaining<'info> {
             Clock::get()?.unix_timestamp < self.task_account.deadline,
             TaskError::DeadlinePassed
         );
-        **self.creator.try_borrow_mut_lamports()? += self.reward_vault.balance;
+        unsolicited_lamports(&self.reward_vault)?;
+
+        // Rewards locked for submitted responses stay in the vault
+        let refundable = self
+            .reward_vault
+            .balance
+            .checked_sub(self.reward_vault.locked_balance)
+            .ok_or(RewardError::VaultBalanceMismatch)?;
+
+        // Sponsors claim their share separately, the creator receives the rest
+        let creator_refund = release_surplus(&mut self.reward_vault, refundable)?;
+
+        **self.creator.try_borrow_mut_lamports()? += creator_refund;
         **self
             .reward_vault
             .to_account_info()
-            .try_borrow_mut_lamports()? -= self.reward_vault.balance;
+            .try_borrow_mut_lamports()? -= creator_refund;
 
-        self.reward_vault.balance = 0;
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
+        }
+
+        // Rewards still locked are owed to pending responses, which can be
+        // settled later; the vault settles once they are
+        let bonus = settle_vault(&mut self.reward_vault, &self.creator.to_account_info())?
+            .unwrap_or(0);
+        let refunded = creator_refund.saturating_add(bonus);
+
+        // The task takes no new submissions or deposits once refunded
+        if self.task_account.is_cancelled {
+            self.creator_profile.record_refund(refunded);
+        } else {
+            self.task_account.is_cancelled = true;
+            self.creator_profile.record_cancellation(refunded);
+            self.protocol_stats.record_task_closed();
+        }
+        self.protocol_stats.record_refund(refundable.saturating_add(bonus));
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSurplus<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump,
+        constraint = task_account.is_complete || task_account.is_cancelled @ RewardError::TaskNotComplete
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    // Epoch tasks are owned by a template and use refund_epoch instead
+    #[account(mut, address = task_account.creator)]
+    pub creator: SystemAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Address and contents are checked by update_creator_profile
+    pub creator_profile: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    // Anyone can crank; the surplus only ever goes to the creator and sponsors
+    pub signer: Signer<'info>,
+}
+
+impl<'info> WithdrawSurplus<'info> {
+    // Returns what a finished task no longer owes anyone: the lamports left
+    // over from graded-down, rejected or unsubmitted responses.
+    pub fn withdraw_surplus(&mut self) -> Result<()> {
+        unsolicited_lamports(&self.reward_vault)?;
+
+        let surplus = self
+            .reward_vault
+            .balance
+            .checked_sub(self.reward_vault.locked_balance)
+            .ok_or(RewardError::VaultBalanceMismatch)?;
+
+        // Without surplus, the call is only useful to settle or close the vault
+        let vault = &self.reward_vault;
+        let settleable =
+            vault.locked_balance == 0 && (!vault.is_settled || vault.sponsor_count == 0);
+        require!(surplus > 0 || settleable, RewardError::NoSurplus);
+
+        let creator_share = release_surplus(&mut self.reward_vault, surplus)?;
+
+        **self.creator.try_borrow_mut_lamports()? += creator_share;
+        **self
+            .reward_vault
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= creator_share;
+
+        let bonus = settle_vault(&mut self.reward_vault, &self.creator.to_account_info())?
+            .unwrap_or(0);
+        update_creator_profile(&self.creator_profile, &self.creator.key(), |profile| {
+            profile.record_refund(creator_share.saturating_add(bonus))
+        })?;
+        self.protocol_stats
+            .record_refund(surplus.saturating_add(bonus));
 
         Ok(())
     }
@@ -190,7 +670,13 @@ pub struct DisburseRewards<'info> {
 
     // Response account to verify the recipient earned rewards
     #[account(
-        seeds = [b"response", task_account.key().as_ref(), recipient.key().as_ref()],
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            recipient.key().as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
         bump = response_account.bump,
         constraint = response_account.is_verified @ TaskError::Unauthorized
     )]
@@ -199,6 +685,28 @@ pub struct DisburseRewards<'info> {
     #[account(mut)]
     pub recipient: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"responder", recipient.key().as_ref()],
+        bump = responder_account.responder_bump
+    )]
+    pub responder_account: Account<'info, Responder>,
+
+    // The task creator, or the template that opened an epoch task
+    #[account(address = task_account.creator)]
+    /// CHECK: Only read to find whose creator profile is credited
+    pub task_creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Address and contents are checked by update_creator_profile
+    pub creator_profile: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"denylist", recipient.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Must be uninitialized, checked by ensure_not_denylisted
+    pub denylist_entry: UncheckedAccount<'info>,
+
     // Admin authorization
     #[account(
         seeds = [b"admin"],
@@ -206,6 +714,13 @@ pub struct DisburseRewards<'info> {
     )]
     pub admin_account: Account<'info, AdminAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     #[account(
         constraint = signer.key() == admin_account.authority @ TaskError::Unauthorized
     )]
@@ -217,8 +732,8 @@ impl<'info> DisburseRewards<'info> {
         let vault = &mut self.reward_vault;
         let task = &self.task_account;
 
-        // Calculate reward amount automatically
-        let reward_amount = task.reward_per_response;
+        // Pay the amount assigned at verification
+        let reward_amount = self.response_account.owed;
 
         require!(
             vault.balance >= reward_amount,
@@ -229,21 +744,94 @@ impl<'info> DisburseRewards<'info> {
 
         require!(self.response_account.is_verified, TaskError::Unauthorized);
 
+        require!(
+            !self.response_account.is_paid,
+            RewardError::RewardAlreadyPaid
+        );
+
+        require!(
+            !self.response_account.is_disputed,
+            DisputeError::PayoutFrozen
+        );
+
+        // Payouts wait until nobody can dispute the verification any more
+        let window_end = self
+            .response_account
+            .verified_at
+            .saturating_add(task.dispute_window);
+        require!(
+            self.response_account.dispute_settled || Clock::get()?.unix_timestamp >= window_end,
+            DisputeError::DisputeWindowOpen
+        );
+
+        ensure_not_denylisted(&self.denylist_entry)?;
+        unsolicited_lamports(vault)?;
+
+        // Bonus is paid from the separate pool and never exceeds what is left in it
+        let bonus_amount = reputation_bonus(
+            reward_amount,
+            task.max_reward_multiplier_bps,
+            self.responder_account.trust_score,
+        )?
+        .min(vault.bonus_balance);
+
+        let payout = reward_amount
+            .checked_add(bonus_amount)
+            .ok_or(RewardError::TransferFailed)?;
+
         // Transfer reward to recipient
-        **self.recipient.try_borrow_mut_lamports()? += reward_amount;
-        **vault.to_account_info().try_borrow_mut_lamports()? -= reward_amount;
+        **self.recipient.try_borrow_mut_lamports()? += payout;
+        **vault.to_account_info().try_borrow_mut_lamports()? -= payout;
 
         vault.balance = vault
             .balance
             .checked_sub(reward_amount)
             .ok_or(RewardError::InsufficientVaultBalance)?;
+        vault.bonus_balance -= bonus_amount;
+
+        self.response_account.is_paid = true;
+        release_reward(vault, &mut self.response_account);
+        self.task_account.responses_paid = self
+            .task_account
+            .responses_paid
+            .checked_add(1)
+            .ok_or(RewardError::TransferFailed)?;
+
+        // Paying a response spends exactly the reward it had locked
+        ensure_locks_covered(&self.task_account, &self.reward_vault)?;
+
+        let owner = profile_owner(&self.task_creator)?;
+        update_creator_profile(&self.creator_profile, &owner, |profile| {
+            profile.record_payout(payout)
+        })?;
+        self.protocol_stats.record_disbursement(payout);
 
         msg!(
-            "Disbursed {} lamports to {} for verified response",
-            reward_amount,
+            "Disbursed {} lamports ({} bonus) to {} for verified response",
+            payout,
+            bonus_amount,
             self.recipient.key()
         );
 
         Ok(())
     }
 }
+
+// Responders at or below the initial trust score earn the base reward; above it
+// the multiplier scales linearly up to the task's maximum at a perfect score.
+fn reputation_bonus(reward_amount: u64, max_multiplier_bps: u16, trust_score: u16) -> Result<u64> {
+    if max_multiplier_bps <= BASE_REWARD_MULTIPLIER_BPS || trust_score <= TRUST_SCORE_INITIAL {
+        return Ok(0);
+    }
+
+    let extra_bps = (max_multiplier_bps - BASE_REWARD_MULTIPLIER_BPS) as u128
+        * (trust_score - TRUST_SCORE_INITIAL) as u128
+        / (TRUST_SCORE_MAX - TRUST_SCORE_INITIAL) as u128;
+
+    let bonus = (reward_amount as u128)
+        .checked_mul(extra_bps)
+        .map(|v| v / BASE_REWARD_MULTIPLIER_BPS as u128)
+        .ok_or(RewardError::TransferFailed)?;
+
+    u64::try_from(bonus).map_err(|_| RewardError::TransferFailed.into())
+}
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    collect_deposit,
+    errors::{RewardError, TaskError},
+    events::{SponsorRefundClaimed, TaskSponsored},
+    states::{AdminAccount, ProtocolStats, RewardVault, SponsorAccount, TaskAccount},
+};
+
+#[derive(Accounts)]
+pub struct SponsorTask<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump,
+        payer = sponsor,
+        space = 8 + RewardVault::INIT_SPACE
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"sponsor", task_account.key().as_ref(), sponsor.key().as_ref()],
+        bump,
+        payer = sponsor,
+        space = 8 + SponsorAccount::INIT_SPACE
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin_authority.key() == admin_account.authority
+    )]
+    /// CHECK: This is safe because we check it matches admin_account.authority
+    pub admin_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SponsorTask<'info> {
+    pub fn sponsor_task(&mut self, amount: u64, bumps: SponsorTaskBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(!self.task_account.is_cancelled, TaskError::TaskCancelled);
+        require!(now < self.task_account.deadline, TaskError::DeadlinePassed);
+
+        // Referral shares reward integrators for bringing in creators, so
+        // third-party sponsorships pay the full platform fee to the admin
+        let net_deposit = collect_deposit(
+            &self.sponsor,
+            &self.admin_authority,
+            &self.reward_vault.to_account_info(),
+            &self.system_program,
+            amount,
+        )?;
+
+        let vault = &mut self.reward_vault;
+        vault.task_bump = self.task_account.task_bump;
+        vault.bump = bumps.reward_vault;
+        vault.balance = vault
+            .balance
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+        vault.deposited = vault
+            .deposited
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+        vault.sponsored = vault
+            .sponsored
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+
+        let sponsor_account = &mut self.sponsor_account;
+        if sponsor_account.sponsor == Pubkey::default() {
+            sponsor_account.task = self.task_account.key();
+            sponsor_account.sponsor = self.sponsor.key();
+            sponsor_account.bump = bumps.sponsor_account;
+            vault.sponsor_count = vault
+                .sponsor_count
+                .checked_add(1)
+                .ok_or(RewardError::TransferFailed)?;
+        }
+        sponsor_account.contributed = sponsor_account
+            .contributed
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+
+        self.protocol_stats
+            .record_deposit(net_deposit, amount - net_deposit);
+
+        emit!(TaskSponsored {
+            task: self.task_account.key(),
+            sponsor: self.sponsor.key(),
+            amount: net_deposit,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimSponsorRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", sponsor_account.task.as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        has_one = sponsor,
+        seeds = [b"sponsor", sponsor_account.task.as_ref(), sponsor.key().as_ref()],
+        bump = sponsor_account.bump
+    )]
+    pub sponsor_account: Account<'info, SponsorAccount>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+}
+
+impl<'info> ClaimSponsorRefund<'info> {
+    // Pays the sponsor's pro-rata share of everything released so far. Once
+    // the vault is settled nothing more can be released, so the claim also
+    // closes the sponsor's record.
+    pub fn claim_sponsor_refund(&mut self) -> Result<()> {
+        let vault = &mut self.reward_vault;
+        let sponsor_account = &mut self.sponsor_account;
+
+        let entitled = u128::from(vault.sponsor_released)
+            .checked_mul(u128::from(sponsor_account.contributed))
+            .and_then(|v| v.checked_div(u128::from(vault.sponsored)))
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(RewardError::TransferFailed)?;
+        let amount = entitled
+            .saturating_sub(sponsor_account.claimed)
+            .min(vault.sponsor_pool);
+        require!(amount > 0 || vault.is_settled, RewardError::NoSponsorRefund);
+
+        **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **self.sponsor.try_borrow_mut_lamports()? += amount;
+        vault.sponsor_pool -= amount;
+        sponsor_account.claimed = sponsor_account.claimed.saturating_add(amount);
+
+        emit!(SponsorRefundClaimed {
+            task: sponsor_account.task,
+            sponsor: self.sponsor.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        if vault.is_settled {
+            vault.sponsor_count = vault.sponsor_count.saturating_sub(1);
+            sponsor_account.close(self.sponsor.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
+
+// Takes `amount` out of the vault balance and splits it between the creator
+// and the sponsors in proportion to their deposits. The sponsors' share stays
+// in the vault until each of them claims it; the creator's share is returned
+// for the caller to pay out.
+pub(crate) fn release_surplus(vault: &mut RewardVault, amount: u64) -> Result<u64> {
+    let sponsor_share = u128::from(amount)
+        .checked_mul(u128::from(vault.sponsored))
+        .and_then(|v| v.checked_div(u128::from(vault.deposited)))
+        .and_then(|v| u64::try_from(v).ok())
+        .unwrap_or(0);
+
+    vault.balance = vault
+        .balance
+        .checked_sub(amount)
+        .ok_or(RewardError::VaultBalanceMismatch)?;
+    vault.sponsor_pool = vault
+        .sponsor_pool
+        .checked_add(sponsor_share)
+        .ok_or(RewardError::TransferFailed)?;
+    vault.sponsor_released = vault
+        .sponsor_released
+        .checked_add(sponsor_share)
+        .ok_or(RewardError::TransferFailed)?;
+
+    Ok(amount - sponsor_share)
+}
+
+// Called once a finished task has been refunded. With nothing locked, the
+// bonus pool goes back to the creator and the vault is settled; it closes as
+// soon as no sponsor records are left, which may take a later call. Returns
+// the bonus returned, or None while rewards are still locked.
+pub(crate) fn settle_vault<'info>(
+    vault: &mut Account<'info, RewardVault>,
+    creator: &AccountInfo<'info>,
+) -> Result<Option<u64>> {
+    if vault.locked_balance != 0 {
+        return Ok(None);
+    }
+
+    let bonus = vault.bonus_balance;
+    if vault.sponsor_count == 0 {
+        vault.close(creator.clone())?;
+    } else {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= bonus;
+        **creator.try_borrow_mut_lamports()? += bonus;
+        vault.bonus_balance = 0;
+        vault.is_settled = true;
+    }
+
+    Ok(Some(bonus))
+}
@@ -1,8 +1,21 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::TaskError,
-    states::{AdminAccount, TaskAccount}
+    cid::Cid,
+    constants::{
+        AGGREGATION_TRIM_BPS, DISPUTE_WINDOW_SECONDS, MAX_AGGREGATED_RESPONSES,
+        MAX_DISPUTE_WINDOW_SECONDS, MAX_INPUT_SIZE, TRUST_SCORE_MAX,
+    },
+    errors::{DisputeError, ErrorCode, TaskError},
+    escrowed_balance,
+    ensure_fully_funded,
+    events::TaskUpdated,
+    funding_shortfall,
+    gross_deposit,
+    states::{
+        AdminAccount, AggregationMethod, CreatorProfile, ProtocolStats, ResponseAccount,
+        TaskAccount, TaskAggregate,
+    },
 };
 
 #[derive(Accounts)]
@@ -21,6 +34,22 @@ pub struct CreateTask<'info> {
     pub creator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    // Optional and trailing so clients built before profiles and stats existed
+    // keep working. Without the profile, create_task_auto may later pick this ID.
+    #[account(
+        mut,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Option<Account<'info, CreatorProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
 }
 
 impl<'info> CreateTask<'info> {
@@ -33,47 +62,107 @@ impl<'info> CreateTask<'info> {
         cid: String,
         bumps: CreateTaskBumps,
     ) -> Result<()> {
-        // Ensure the task ID is non-zero
-        if task_id == 0 {
-            return Err(TaskError::InvalidTaskId.into());
-        }
+        let task = new_task(
+            self.creator.key(),
+            task_id,
+            reward_per_response,
+            max_responses,
+            deadline,
+            cid,
+            bumps.task_account,
+        )?;
 
-        // Ensure the reward is greater than zero
-        if reward_per_response == 0 {
-            return Err(TaskError::InvalidReward.into());
-        }
+        // Initialize the task account
+        self.task_account.set_inner(task);
 
-        // Ensure max responses is a positive number
-        if max_responses == 0 {
-            return Err(TaskError::InvalidMaxResponses.into());
+        if let Some(creator_profile) = self.creator_profile.as_mut() {
+            creator_profile.record_task_created();
+            // Keep create_task_auto from picking an ID that is already taken
+            creator_profile.next_task_id = creator_profile
+                .next_task_id
+                .max(task_id.saturating_add(1));
         }
-
-        // Ensure deadline is in the future
-        let current_timestamp = Clock::get()?.unix_timestamp;
-        if deadline <= current_timestamp {
-            return Err(TaskError::InvalidDeadline.into());
+        if let Some(protocol_stats) = self.protocol_stats.as_mut() {
+            protocol_stats.record_task_opened();
         }
 
-        // Ensure CID is not empty
-        if cid.trim().is_empty() {
-            return Err(TaskError::InvalidCID.into());
+        msg!(
+            "Task created successfully {}",
+            self.task_account.key().to_string()
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateTaskAuto<'info> {
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        init,
+        seeds = [b"task", creator.key().as_ref(), &creator_profile.auto_task_id().to_le_bytes()],
+        bump,
+        payer = creator,
+        space = 8 + TaskAccount::INIT_SPACE
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTaskAuto<'info> {
+    pub fn create_task_auto(
+        &mut self,
+        reward_per_response: u64,
+        max_responses: u16,
+        deadline: i64,
+        cid: String,
+        bumps: CreateTaskAutoBumps,
+    ) -> Result<()> {
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
         }
 
-        // Initialize the task account
-        self.task_account.set_inner(TaskAccount {
+        let task_id = self.creator_profile.auto_task_id();
+        let task = new_task(
+            self.creator.key(),
             task_id,
-            creator: self.creator.key(),
             reward_per_response,
             max_responses,
             deadline,
-            responses_received: 0,
-            is_complete: false,
-            task_bump: bumps.task_account,
             cid,
-        });
+            bumps.task_account,
+        )?;
+
+        self.task_account.set_inner(task);
+        self.creator_profile.record_task_created();
+        self.protocol_stats.record_task_opened();
+        self.creator_profile.next_task_id = task_id
+            .checked_add(1)
+            .ok_or(TaskError::InvalidTaskId)?;
 
         msg!(
-            "Task created successfully {}",
+            "Task {} created successfully {}",
+            task_id,
             self.task_account.key().to_string()
         );
 
@@ -81,6 +170,78 @@ impl<'info> CreateTask<'info> {
     }
 }
 
+// Validates the create arguments and builds a task with default settings
+fn new_task(
+    creator: Pubkey,
+    task_id: u64,
+    reward_per_response: u64,
+    max_responses: u16,
+    deadline: i64,
+    cid: String,
+    task_bump: u8,
+) -> Result<TaskAccount> {
+    // Ensure the task ID is non-zero
+    if task_id == 0 {
+        return Err(TaskError::InvalidTaskId.into());
+    }
+
+    // Ensure the reward is greater than zero
+    if reward_per_response == 0 {
+        return Err(TaskError::InvalidReward.into());
+    }
+
+    // Ensure max responses is a positive number
+    if max_responses == 0 {
+        return Err(TaskError::InvalidMaxResponses.into());
+    }
+
+    // Ensure deadline is in the future
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    if deadline <= current_timestamp {
+        return Err(TaskError::InvalidDeadline.into());
+    }
+
+    // Ensure CID is not empty
+    if cid.trim().is_empty() {
+        return Err(TaskError::InvalidCID.into());
+    }
+
+    // Ensure CID is a well-formed content identifier
+    let cid = Cid::parse(&cid)?;
+
+    let task = TaskAccount {
+        task_id,
+        creator,
+        reward_per_response,
+        max_responses,
+        deadline,
+        responses_received: 0,
+        responses_paid: 0,
+        is_complete: false,
+        is_cancelled: false,
+        task_bump,
+        min_trust_score: 0,
+        max_reward_multiplier_bps: 0,
+        allowlist_root: None,
+        reveal_window: 0,
+        dispute_window: DISPUTE_WINDOW_SECONDS,
+        aggregation: AggregationMethod::None,
+        outlier_tolerance: 0,
+        aggregate_result: None,
+        max_responses_per_responder: 1,
+        prerequisites: Vec::new(),
+        prerequisite_outputs: Vec::new(),
+        cid,
+        input: Vec::new(),
+    };
+
+    // Also rejects liabilities too large to ever escrow
+    let required = gross_deposit(funding_shortfall(&task, 0)?)?;
+    msg!("Deposit {} lamports to open the task for submissions", required);
+
+    Ok(task)
+}
+
 #[derive(Accounts)]
 pub struct MarkTaskComplete<'info> {
     #[account(
@@ -97,6 +258,13 @@ pub struct MarkTaskComplete<'info> {
     )]
     pub admin_account: Account<'info, AdminAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 }
@@ -113,12 +281,197 @@ impl<'info> MarkTaskComplete<'info> {
 
         require!(!task.is_complete, TaskError::TaskAlreadyComplete);
 
+        // A cancelled task was already counted as closed when it was refunded
+        if !task.is_cancelled {
+            self.protocol_stats.record_task_closed();
+        }
         self.task_account.is_complete = true;
 
         Ok(())
     }
 }
 
+#[derive(Accounts)]
+pub struct AggregateResponses<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump,
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"aggregate", task_account.key().as_ref()],
+        bump,
+        payer = signer,
+        space = 8 + TaskAggregate::INIT_SPACE
+    )]
+    pub task_aggregate: Account<'info, TaskAggregate>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump,
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AggregateResponses<'info> {
+    // Responses are passed as writable remaining accounts in batches that fit
+    // a transaction. Each is counted once, and values of verified responses are
+    // collected until every response has been counted, which stores the result.
+    pub fn aggregate_responses(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        bumps: AggregateResponsesBumps,
+    ) -> Result<()> {
+        let task = &self.task_account;
+
+        require!(
+            self.admin_account.authority == self.signer.key()
+                || self.signer.key() == task.creator,
+            TaskError::Unauthorized
+        );
+        require!(task.is_complete, TaskError::NotEnoughResponses);
+        require!(
+            task.aggregation != AggregationMethod::None,
+            TaskError::AggregationNotEnabled
+        );
+        require!(task.aggregate_result.is_none(), TaskError::AlreadyAggregated);
+
+        let aggregate = &mut self.task_aggregate;
+        aggregate.task = task.key();
+        aggregate.bump = bumps.task_aggregate;
+
+        for info in remaining_accounts {
+            let mut response = task_response(task, info)?;
+            require!(!response.is_aggregated, TaskError::InvalidResponseAccounts);
+
+            if response.is_verified && !response.is_rejected {
+                if let Some(value) = response.numeric_value {
+                    require!(
+                        aggregate.values.len() < MAX_AGGREGATED_RESPONSES,
+                        TaskError::TooManyAggregatedResponses
+                    );
+                    aggregate.values.push(value);
+                }
+            }
+
+            // Written back right away so a duplicate in the same batch is caught
+            response.is_aggregated = true;
+            response.exit(&crate::ID)?;
+            aggregate.responses_counted += 1;
+        }
+
+        if aggregate.responses_counted < task.responses_received {
+            msg!(
+                "Counted {} of {} responses",
+                aggregate.responses_counted,
+                task.responses_received
+            );
+            return Ok(());
+        }
+
+        require!(!aggregate.values.is_empty(), TaskError::NotEnoughVerifiedResponses);
+        let mut values = aggregate.values.clone();
+        values.sort_unstable();
+
+        let result = aggregate_values(&values, task.aggregation);
+        self.task_account.aggregate_result = Some(result);
+
+        msg!("Aggregated {} values into {}", values.len(), result);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct FlagOutliers<'info> {
+    #[account(
+        seeds = [b"task", task_account.creator.as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump,
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+}
+
+impl<'info> FlagOutliers<'info> {
+    // Permissionless since the result is fixed once aggregated. Only verified,
+    // non-rejected responses too far from it are flagged.
+    pub fn flag_outliers(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let task = &self.task_account;
+        let result = task.aggregate_result.ok_or(TaskError::NotAggregated)?;
+
+        require!(task.outlier_tolerance > 0, TaskError::AggregationNotEnabled);
+
+        for info in remaining_accounts {
+            let mut response = task_response(task, info)?;
+            if !response.is_verified || response.is_rejected || response.is_outlier {
+                continue;
+            }
+            if let Some(value) = response.numeric_value {
+                if value.abs_diff(result) > task.outlier_tolerance {
+                    response.is_outlier = true;
+                    response.exit(&crate::ID)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Loads a response passed as a remaining account, checking it belongs to the task
+fn task_response<'info>(
+    task: &Account<'info, TaskAccount>,
+    info: &'info AccountInfo<'info>,
+) -> Result<Account<'info, ResponseAccount>> {
+    let response = Account::<ResponseAccount>::try_from(info)?;
+    let expected = Pubkey::create_program_address(
+        &[
+            b"response",
+            task.key().as_ref(),
+            response.responder.as_ref(),
+            &response.index.to_le_bytes(),
+            &[response.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| TaskError::InvalidResponseAccounts)?;
+    require_keys_eq!(info.key(), expected, TaskError::InvalidResponseAccounts);
+
+    Ok(response)
+}
+
+// `values` must be sorted and non-empty
+fn aggregate_values(values: &[i64], method: AggregationMethod) -> i64 {
+    let mean = |slice: &[i64]| {
+        let sum: i128 = slice.iter().map(|v| *v as i128).sum();
+        (sum / slice.len() as i128) as i64
+    };
+    let median = |slice: &[i64]| {
+        let mid = slice.len() / 2;
+        if slice.len() % 2 == 1 {
+            slice[mid]
+        } else {
+            ((slice[mid - 1] as i128 + slice[mid] as i128) / 2) as i64
+        }
+    };
+
+    match method {
+        AggregationMethod::Mean => mean(values),
+        AggregationMethod::TrimmedMean => {
+            let trim = values.len() * AGGREGATION_TRIM_BPS / 10_000;
+            mean(&values[trim..values.len() - trim])
+        }
+        AggregationMethod::Median | AggregationMethod::None => median(values),
+    }
+}
+
 #[derive(Accounts)]
 #[instruction(task_id: u64)]
 pub struct UpdateTask<'info> {
@@ -129,6 +482,13 @@ pub struct UpdateTask<'info> {
     )]
     pub task_account: Account<'info, TaskAccount>,
 
+    #[account(
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Empty until the task is funded, read by escrowed_balance
+    pub reward_vault: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub creator: Signer<'info>,
 
@@ -170,6 +530,46 @@ impl<'info> UpdateTask<'info> {
             return Err(TaskError::InvalidCID.into());
         }
 
+        // Ensure CID is a well-formed content identifier
+        let cid = Cid::parse(&cid)?;
+
+        let task = &self.task_account;
+
+        require!(!task.is_complete, TaskError::TaskAlreadyComplete);
+        require!(!task.is_cancelled, TaskError::TaskCancelled);
+        require!(
+            max_responses >= task.responses_received,
+            TaskError::MaxResponsesBelowReceived
+        );
+        require!(
+            task.aggregation == AggregationMethod::None
+                || max_responses as usize <= MAX_AGGREGATED_RESPONSES,
+            TaskError::TooManyAggregatedResponses
+        );
+        // Responders already submitted against the advertised reward, and
+        // their locked rewards were sized from it
+        require!(
+            task.responses_received == 0 || reward_per_response == task.reward_per_response,
+            TaskError::RewardChangeNotAllowed
+        );
+
+        // A task open for submissions must stay fully funded after the update
+        let vault_balance = escrowed_balance(&self.reward_vault)?;
+        let was_funded = funding_shortfall(task, vault_balance)? == 0;
+
+        let event = TaskUpdated {
+            task: task.key(),
+            old_reward_per_response: task.reward_per_response,
+            new_reward_per_response: reward_per_response,
+            old_max_responses: task.max_responses,
+            new_max_responses: max_responses,
+            old_deadline: task.deadline,
+            new_deadline: deadline,
+            old_cid: task.cid,
+            new_cid: cid,
+            timestamp: current_timestamp,
+        };
+
         // Update the task account
         self.task_account.set_inner(TaskAccount {
             task_id: self.task_account.task_id,
@@ -178,16 +578,283 @@ impl<'info> UpdateTask<'info> {
             max_responses,
             deadline,
             responses_received: self.task_account.responses_received,
+            responses_paid: self.task_account.responses_paid,
             is_complete: self.task_account.is_complete,
+            is_cancelled: self.task_account.is_cancelled,
             task_bump: self.task_account.task_bump,
+            min_trust_score: self.task_account.min_trust_score,
+            max_reward_multiplier_bps: self.task_account.max_reward_multiplier_bps,
+            allowlist_root: self.task_account.allowlist_root,
+            reveal_window: self.task_account.reveal_window,
+            dispute_window: self.task_account.dispute_window,
+            aggregation: self.task_account.aggregation,
+            outlier_tolerance: self.task_account.outlier_tolerance,
+            aggregate_result: self.task_account.aggregate_result,
+            max_responses_per_responder: self.task_account.max_responses_per_responder,
+            prerequisites: self.task_account.prerequisites.clone(),
+            prerequisite_outputs: self.task_account.prerequisite_outputs.clone(),
             cid,
+            input: self.task_account.input.clone(),
         });
 
+        if was_funded {
+            ensure_fully_funded(&self.task_account, vault_balance)?;
+        }
+
+        emit!(event);
+
         msg!(
-            "Task created successfully {}",
+            "Task updated successfully {}",
             self.task_account.key().to_string()
         );
 
         Ok(())
     }
 }
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetMinTrustScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetMinTrustScore<'info> {
+    pub fn set_min_trust_score(&mut self, _task_id: u64, min_trust_score: u16) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(
+            min_trust_score <= TRUST_SCORE_MAX,
+            TaskError::InvalidTrustScore
+        );
+
+        self.task_account.min_trust_score = min_trust_score;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetTaskAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetTaskAllowlist<'info> {
+    pub fn set_task_allowlist(
+        &mut self,
+        _task_id: u64,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+
+        self.task_account.allowlist_root = allowlist_root;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetCommitReveal<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetCommitReveal<'info> {
+    pub fn set_commit_reveal(&mut self, _task_id: u64, reveal_window: i64) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        // Switching modes would leave existing responses in the wrong shape
+        require!(
+            self.task_account.responses_received == 0,
+            TaskError::TaskHasResponses
+        );
+        require!(reveal_window >= 0, TaskError::InvalidDeadline);
+        require!(
+            reveal_window == 0 || self.task_account.aggregation == AggregationMethod::None,
+            TaskError::IncompatibleTaskModes
+        );
+
+        self.task_account.reveal_window = reveal_window;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetDisputeWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetDisputeWindow<'info> {
+    pub fn set_dispute_window(&mut self, _task_id: u64, dispute_window: i64) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        // Responders submit knowing how long their payout can be challenged
+        require!(
+            self.task_account.responses_received == 0,
+            TaskError::TaskHasResponses
+        );
+        require!(
+            (0..=MAX_DISPUTE_WINDOW_SECONDS).contains(&dispute_window),
+            DisputeError::InvalidDisputeWindow
+        );
+
+        self.task_account.dispute_window = dispute_window;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetMaxResponsesPerResponder<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetMaxResponsesPerResponder<'info> {
+    pub fn set_max_responses_per_responder(
+        &mut self,
+        _task_id: u64,
+        max_responses_per_responder: u16,
+    ) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(
+            max_responses_per_responder > 0,
+            TaskError::InvalidMaxResponses
+        );
+
+        self.task_account.max_responses_per_responder = max_responses_per_responder;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct SetTaskAggregation<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    pub creator: Signer<'info>,
+}
+
+impl<'info> SetTaskAggregation<'info> {
+    pub fn set_task_aggregation(
+        &mut self,
+        _task_id: u64,
+        aggregation: AggregationMethod,
+        outlier_tolerance: u64,
+    ) -> Result<()> {
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(
+            self.task_account.responses_received == 0,
+            TaskError::TaskHasResponses
+        );
+        require!(
+            aggregation == AggregationMethod::None || self.task_account.reveal_window == 0,
+            TaskError::IncompatibleTaskModes
+        );
+        require!(
+            aggregation == AggregationMethod::None
+                || self.task_account.max_responses as usize <= MAX_AGGREGATED_RESPONSES,
+            TaskError::TooManyAggregatedResponses
+        );
+
+        self.task_account.aggregation = aggregation;
+        self.task_account.outlier_tolerance = outlier_tolerance;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64, input: Vec<u8>)]
+pub struct SetTaskInput<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", creator.key().as_ref(), &task_id.to_le_bytes()],
+        bump = task_account.task_bump,
+        realloc = 8 + TaskAccount::INIT_SPACE + input.len(),
+        realloc::payer = creator,
+        realloc::zero = false
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetTaskInput<'info> {
+    pub fn set_task_input(&mut self, _task_id: u64, input: Vec<u8>) -> Result<()> {
+        require!(input.len() <= MAX_INPUT_SIZE, ErrorCode::InputTooLarge);
+        require!(
+            !self.task_account.is_complete,
+            TaskError::TaskAlreadyComplete
+        );
+        require!(
+            self.task_account.responses_received == 0,
+            TaskError::TaskHasResponses
+        );
+
+        self.task_account.input = input;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,557 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    calculate_platform_fee,
+    cid::Cid,
+    collect_with_fee,
+    constants::DISPUTE_WINDOW_SECONDS,
+    errors::{RewardError, TaskError, TemplateError},
+    states::{
+        AdminAccount, AggregationMethod, CreatorProfile, ProtocolStats, ReferrerAccrual,
+        RewardVault, TaskAccount, TaskTemplate,
+    },
+    referral_fee, release_surplus, settle_vault, unsolicited_lamports,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TaskTemplateParams {
+    pub reward_per_response: u64,
+    pub max_responses: u16,
+    pub first_start: i64,
+    pub interval: i64,
+    pub epoch_duration: i64,
+    pub total_epochs: u16,
+    pub cid: String,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateTaskTemplate<'info> {
+    #[account(
+        init,
+        seeds = [b"template", creator.key().as_ref(), &template_id.to_le_bytes()],
+        bump,
+        payer = creator,
+        space = 8 + TaskTemplate::INIT_SPACE
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTaskTemplate<'info> {
+    pub fn create_task_template(
+        &mut self,
+        template_id: u64,
+        params: TaskTemplateParams,
+        bumps: CreateTaskTemplateBumps,
+    ) -> Result<()> {
+        require!(template_id != 0, TaskError::InvalidTaskId);
+        require!(params.reward_per_response != 0, TaskError::InvalidReward);
+        require!(params.max_responses != 0, TaskError::InvalidMaxResponses);
+        require!(params.total_epochs != 0, TemplateError::InvalidSchedule);
+        // Epochs must not overlap
+        require!(
+            params.epoch_duration > 0 && params.epoch_duration <= params.interval,
+            TemplateError::InvalidSchedule
+        );
+        require!(!params.cid.trim().is_empty(), TaskError::InvalidCID);
+
+        // Every epoch start and escrow must be computable without overflow
+        let last_deadline = params
+            .interval
+            .checked_mul(i64::from(params.total_epochs))
+            .and_then(|offset| offset.checked_add(params.epoch_duration))
+            .and_then(|offset| params.first_start.checked_add(offset));
+        let epoch_escrow = params
+            .reward_per_response
+            .checked_mul(u64::from(params.max_responses));
+        require!(
+            last_deadline.is_some() && epoch_escrow.is_some(),
+            TemplateError::InvalidSchedule
+        );
+
+        let cid = Cid::parse(&params.cid)?;
+
+        self.template.set_inner(TaskTemplate {
+            template_id,
+            creator: self.creator.key(),
+            reward_per_response: params.reward_per_response,
+            max_responses: params.max_responses,
+            first_start: params.first_start,
+            interval: params.interval,
+            epoch_duration: params.epoch_duration,
+            total_epochs: params.total_epochs,
+            epochs_opened: 0,
+            live_epochs: 0,
+            bump: bumps.template,
+            cid,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct FundTaskTemplate<'info> {
+    #[account(
+        seeds = [b"template", creator.key().as_ref(), &template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"vault", template.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + RewardVault::INIT_SPACE
+    )]
+    pub template_vault: Account<'info, RewardVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    // Required when the creator was referred, receives part of the platform fee
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer_accrual.referrer.as_ref()],
+        bump = referrer_accrual.bump
+    )]
+    pub referrer_accrual: Option<Account<'info, ReferrerAccrual>>,
+
+    #[account(
+        seeds = [b"referral_config"],
+        bump
+    )]
+    /// CHECK: May be uninitialized, read by referral_fee
+    pub referral_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin_authority.key() == admin_account.authority
+    )]
+    /// CHECK: This is safe because we check it matches admin_account.authority
+    pub admin_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundTaskTemplate<'info> {
+    pub fn fund_task_template(
+        &mut self,
+        _template_id: u64,
+        amount: u64,
+        bumps: FundTaskTemplateBumps,
+    ) -> Result<()> {
+        let platform_fee = calculate_platform_fee(amount)?;
+        let referral_fee = referral_fee(
+            &self.creator,
+            self.creator_profile.referrer,
+            self.referrer_accrual.as_mut(),
+            &self.referral_config,
+            &self.system_program,
+            platform_fee,
+        )?;
+
+        let net_deposit = collect_with_fee(
+            &self.creator,
+            &self.admin_authority,
+            &self.template_vault.to_account_info(),
+            &self.system_program,
+            amount
+                .checked_sub(referral_fee)
+                .ok_or(RewardError::TransferFailed)?,
+            platform_fee
+                .checked_sub(referral_fee)
+                .ok_or(RewardError::TransferFailed)?,
+        )?;
+
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
+        }
+        self.creator_profile
+            .record_deposit(net_deposit, amount - net_deposit);
+        self.protocol_stats
+            .record_deposit(net_deposit, amount - net_deposit);
+
+        self.template_vault.task_bump = self.template.bump;
+        self.template_vault.balance = self
+            .template_vault
+            .balance
+            .checked_add(net_deposit)
+            .ok_or(RewardError::TransferFailed)?;
+        self.template_vault.bump = bumps.template_vault;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OpenNextEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"template", template.creator.as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", template.key().as_ref()],
+        bump = template_vault.bump
+    )]
+    pub template_vault: Account<'info, RewardVault>,
+
+    #[account(
+        init,
+        seeds = [b"task", template.key().as_ref(), &template.next_epoch().to_le_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + TaskAccount::INIT_SPACE
+    )]
+    pub epoch_task: Account<'info, TaskAccount>,
+
+    #[account(
+        init,
+        seeds = [b"vault", epoch_task.key().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + RewardVault::INIT_SPACE
+    )]
+    pub epoch_vault: Account<'info, RewardVault>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    // Anyone can crank; they only cover the rent of the new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenNextEpoch<'info> {
+    pub fn open_next_epoch(&mut self, bumps: OpenNextEpochBumps) -> Result<()> {
+        let template = &self.template;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            template.epochs_opened < template.total_epochs,
+            TemplateError::AllEpochsOpened
+        );
+
+        let start = template
+            .next_epoch_start()
+            .ok_or(TemplateError::InvalidSchedule)?;
+        require!(now >= start, TemplateError::EpochNotStarted);
+
+        // The window follows the schedule, a missed epoch is skipped instead
+        let deadline = template
+            .next_epoch_deadline()
+            .ok_or(TemplateError::InvalidSchedule)?;
+        require!(now < deadline, TemplateError::EpochWindowPassed);
+
+        let escrow = template
+            .epoch_escrow()
+            .ok_or(TemplateError::InvalidSchedule)?;
+        require!(
+            self.template_vault.balance >= escrow,
+            TemplateError::InsufficientTemplateFunds
+        );
+
+        self.epoch_task.set_inner(TaskAccount {
+            task_id: template.next_epoch(),
+            creator: template.key(),
+            reward_per_response: template.reward_per_response,
+            max_responses: template.max_responses,
+            deadline,
+            responses_received: 0,
+            responses_paid: 0,
+            is_complete: false,
+            is_cancelled: false,
+            task_bump: bumps.epoch_task,
+            min_trust_score: 0,
+            max_reward_multiplier_bps: 0,
+            allowlist_root: None,
+            reveal_window: 0,
+            dispute_window: DISPUTE_WINDOW_SECONDS,
+            aggregation: AggregationMethod::None,
+            outlier_tolerance: 0,
+            aggregate_result: None,
+            max_responses_per_responder: 1,
+            prerequisites: Vec::new(),
+            prerequisite_outputs: Vec::new(),
+            cid: template.cid,
+            input: Vec::new(),
+        });
+
+        **self
+            .template_vault
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= escrow;
+        **self
+            .epoch_vault
+            .to_account_info()
+            .try_borrow_mut_lamports()? += escrow;
+
+        self.template_vault.balance -= escrow;
+        self.epoch_vault.set_inner(RewardVault {
+            task_bump: bumps.epoch_task,
+            balance: escrow,
+            bonus_balance: 0,
+            locked_balance: 0,
+            deposited: escrow,
+            sponsored: 0,
+            sponsor_count: 0,
+            sponsor_pool: 0,
+            sponsor_released: 0,
+            is_settled: false,
+            bump: bumps.epoch_vault,
+        });
+
+        self.template.epochs_opened += 1;
+        self.template.live_epochs += 1;
+        self.protocol_stats.record_task_opened();
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SkipEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"template", template.creator.as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+}
+
+impl<'info> SkipEpoch<'info> {
+    // Anyone can move the schedule past an epoch nobody opened in time. Its
+    // escrow stays in the template vault and is refunded when the template closes.
+    pub fn skip_epoch(&mut self) -> Result<()> {
+        let template = &mut self.template;
+
+        require!(
+            template.epochs_opened < template.total_epochs,
+            TemplateError::AllEpochsOpened
+        );
+
+        let deadline = template
+            .next_epoch_deadline()
+            .ok_or(TemplateError::InvalidSchedule)?;
+        require!(
+            Clock::get()?.unix_timestamp >= deadline,
+            TemplateError::EpochWindowOpen
+        );
+
+        msg!("Skipped epoch {}", template.next_epoch());
+        template.epochs_opened += 1;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RefundEpoch<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"template", creator.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(
+        mut,
+        seeds = [b"task", template.key().as_ref(), &epoch_task.task_id.to_le_bytes()],
+        bump = epoch_task.task_bump
+    )]
+    pub epoch_task: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", epoch_task.key().as_ref()],
+        bump = epoch_vault.bump
+    )]
+    pub epoch_vault: Account<'info, RewardVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundEpoch<'info> {
+    // Epoch tasks are owned by their template, so the template creator stands
+    // in for the task creator. Rewards locked for submitted responses stay in
+    // the vault, and the epoch counts as live until they are settled and the
+    // epoch is refunded again.
+    pub fn refund_epoch(&mut self, bumps: RefundEpochBumps) -> Result<()> {
+        unsolicited_lamports(&self.epoch_vault)?;
+
+        let refundable = self
+            .epoch_vault
+            .balance
+            .checked_sub(self.epoch_vault.locked_balance)
+            .ok_or(RewardError::VaultBalanceMismatch)?;
+
+        // Sponsors claim their share separately, the creator receives the rest
+        let creator_refund = release_surplus(&mut self.epoch_vault, refundable)?;
+
+        **self.creator.try_borrow_mut_lamports()? += creator_refund;
+        **self
+            .epoch_vault
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= creator_refund;
+
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
+        }
+
+        // Refunding an epoch that could still take submissions cancels it,
+        // and an expired epoch completes so its verified responses can be paid
+        let task = &mut self.epoch_task;
+        let open = !task.is_complete && !task.is_cancelled;
+        let cancelled = open && Clock::get()?.unix_timestamp < task.deadline;
+        if cancelled {
+            task.is_cancelled = true;
+        } else if open {
+            task.is_complete = true;
+        }
+        if open {
+            self.protocol_stats.record_task_closed();
+        }
+
+        let was_settled = self.epoch_vault.is_settled;
+        let settled = settle_vault(&mut self.epoch_vault, &self.creator.to_account_info())?;
+        if settled.is_some() && !was_settled {
+            self.template.live_epochs = self.template.live_epochs.saturating_sub(1);
+        }
+
+        let refunded = creator_refund.saturating_add(settled.unwrap_or(0));
+        if cancelled {
+            self.creator_profile.record_cancellation(refunded);
+        } else {
+            self.creator_profile.record_refund(refunded);
+        }
+        self.protocol_stats
+            .record_refund(refundable.saturating_add(settled.unwrap_or(0)));
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseTaskTemplate<'info> {
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"template", creator.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump = template.bump,
+        close = creator
+    )]
+    pub template: Account<'info, TaskTemplate>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", template.key().as_ref()],
+        bump = template_vault.bump,
+        close = creator
+    )]
+    pub template_vault: Account<'info, RewardVault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorProfile::INIT_SPACE
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseTaskTemplate<'info> {
+    // Cancels the epochs not opened yet; the unspent escrow and rent go back
+    // to the creator when both accounts close. Opened epochs must be refunded
+    // first, since their vaults are only reachable through the template.
+    pub fn close_task_template(&mut self, bumps: CloseTaskTemplateBumps) -> Result<()> {
+        require!(
+            self.template.live_epochs == 0,
+            TemplateError::EpochsStillOpen
+        );
+
+        if self.creator_profile.authority == Pubkey::default() {
+            self.creator_profile
+                .initialize(self.creator.key(), bumps.creator_profile);
+        }
+        let unspent = self.template_vault.balance;
+        self.creator_profile.record_refund(unspent);
+        self.protocol_stats.record_refund(unspent);
+        self.template_vault.balance = 0;
+
+        Ok(())
+    }
+}
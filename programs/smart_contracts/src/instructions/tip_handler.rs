@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    calculate_platform_fee, collect_with_fee, ensure_not_denylisted,
+    errors::{DisputeError, RewardError},
+    events::ResponseTipped,
+    states::{AdminAccount, ProtocolStats, ResponseAccount, RewardVault, TaskAccount},
+    unsolicited_lamports, update_creator_profile,
+};
+
+#[derive(Accounts)]
+pub struct TipResponder<'info> {
+    // Only the task's creator can tip its responders. Epoch tasks are owned by
+    // their template, which cannot sign, so they cannot be tipped.
+    #[account(
+        seeds = [b"task", creator.key().as_ref(), &task_account.task_id.to_le_bytes()],
+        bump = task_account.task_bump
+    )]
+    pub task_account: Account<'info, TaskAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"response",
+            task_account.key().as_ref(),
+            recipient.key().as_ref(),
+            &response_account.index.to_le_bytes()
+        ],
+        bump = response_account.bump,
+        constraint = response_account.is_verified @ RewardError::ResponseNotVerified
+    )]
+    pub response_account: Account<'info, ResponseAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", task_account.key().as_ref()],
+        bump = reward_vault.bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"denylist", recipient.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Must be uninitialized, checked by ensure_not_denylisted
+    pub denylist_entry: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Address and contents are checked by update_creator_profile
+    pub creator_profile: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"admin"],
+        bump = admin_account.bump
+    )]
+    pub admin_account: Account<'info, AdminAccount>,
+
+    #[account(
+        mut,
+        constraint = admin_authority.key() == admin_account.authority
+    )]
+    /// CHECK: This is safe because we check it matches admin_account.authority
+    pub admin_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_stats"],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TipResponder<'info> {
+    pub fn tip_responder(&mut self, amount: u64) -> Result<()> {
+        require!(
+            !self.response_account.is_disputed,
+            DisputeError::PayoutFrozen
+        );
+        ensure_not_denylisted(&self.denylist_entry)?;
+        unsolicited_lamports(&self.reward_vault)?;
+
+        // The tip is escrowed and paid out in one step, leaving the vault's
+        // tracked balances untouched. It carries the same platform fee as deposits.
+        let fee = calculate_platform_fee(amount)?;
+        let net_tip = collect_with_fee(
+            &self.creator,
+            &self.admin_authority,
+            &self.reward_vault.to_account_info(),
+            &self.system_program,
+            amount,
+            fee,
+        )?;
+
+        **self.recipient.try_borrow_mut_lamports()? += net_tip;
+        **self.reward_vault.to_account_info().try_borrow_mut_lamports()? -= net_tip;
+
+        self.response_account.tipped = self
+            .response_account
+            .tipped
+            .checked_add(net_tip)
+            .ok_or(RewardError::TransferFailed)?;
+
+        update_creator_profile(&self.creator_profile, &self.creator.key(), |profile| {
+            profile.record_tip(net_tip, fee)
+        })?;
+        self.protocol_stats.record_tip(net_tip, fee);
+
+        emit!(ResponseTipped {
+            task: self.task_account.key(),
+            response: self.response_account.key(),
+            responder: self.recipient.key(),
+            tipper: self.creator.key(),
+            amount: net_tip,
+            fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Tipped {} lamports to {}", net_tip, self.recipient.key());
+
+        Ok(())
+    }
+}
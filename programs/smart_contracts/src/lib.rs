@@ -3,9 +3,12 @@ use anchor_lang::prelude::*;
 
 declare_id!("NDRNySXFhPVKUmSPkxwm92u2KD9qPEeaN8poUSW2LNQ");
 
+pub mod cid;
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod merkle;
 pub mod states;
 
 pub use instructions::*;
@@ -19,6 +22,10 @@ pub mod smart_contracts {
         ctx.accounts.delegate(ctx.bumps)
     }
 
+    pub fn init_protocol_stats(ctx: Context<InitProtocolStats>) -> Result<()> {
+        ctx.accounts.init_protocol_stats(ctx.bumps)
+    }
+
     pub fn create_task(
         ctx: Context<CreateTask>,
         task_id: u64,
@@ -36,6 +43,58 @@ pub mod smart_contracts {
             ctx.bumps,
         )
     }
+
+    pub fn create_task_auto(
+        ctx: Context<CreateTaskAuto>,
+        reward_per_response: u64,
+        max_responses: u16,
+        deadline: i64,
+        cid: String,
+    ) -> Result<()> {
+        ctx.accounts.create_task_auto(
+            reward_per_response,
+            max_responses,
+            deadline,
+            cid,
+            ctx.bumps,
+        )
+    }
+
+    pub fn set_max_responses_per_responder(
+        ctx: Context<SetMaxResponsesPerResponder>,
+        task_id: u64,
+        max_responses_per_responder: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_max_responses_per_responder(task_id, max_responses_per_responder)
+    }
+
+    pub fn set_task_aggregation(
+        ctx: Context<SetTaskAggregation>,
+        task_id: u64,
+        aggregation: AggregationMethod,
+        outlier_tolerance: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_task_aggregation(task_id, aggregation, outlier_tolerance)
+    }
+
+    pub fn set_task_input(ctx: Context<SetTaskInput>, task_id: u64, input: Vec<u8>) -> Result<()> {
+        ctx.accounts.set_task_input(task_id, input)
+    }
+
+    pub fn set_task_prerequisites(
+        ctx: Context<SetTaskPrerequisites>,
+        task_id: u64,
+        prerequisites: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.set_task_prerequisites(task_id, prerequisites)
+    }
+
+    pub fn link_prerequisite_output(ctx: Context<LinkPrerequisiteOutput>, task_id: u64) -> Result<()> {
+        ctx.accounts.link_prerequisite_output(task_id)
+    }
+
     pub fn update_task(
         ctx: Context<UpdateTask>,
         task_id: u64,
@@ -53,28 +112,250 @@ pub mod smart_contracts {
         )
     }
 
+    pub fn create_task_template(
+        ctx: Context<CreateTaskTemplate>,
+        template_id: u64,
+        params: TaskTemplateParams,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_task_template(template_id, params, ctx.bumps)
+    }
+
+    pub fn fund_task_template(
+        ctx: Context<FundTaskTemplate>,
+        template_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .fund_task_template(template_id, amount, ctx.bumps)
+    }
+
+    pub fn open_next_epoch(ctx: Context<OpenNextEpoch>) -> Result<()> {
+        ctx.accounts.open_next_epoch(ctx.bumps)
+    }
+
+    pub fn skip_epoch(ctx: Context<SkipEpoch>) -> Result<()> {
+        ctx.accounts.skip_epoch()
+    }
+
+    pub fn refund_epoch(ctx: Context<RefundEpoch>) -> Result<()> {
+        ctx.accounts.refund_epoch(ctx.bumps)
+    }
+
+    pub fn close_task_template(ctx: Context<CloseTaskTemplate>) -> Result<()> {
+        ctx.accounts.close_task_template(ctx.bumps)
+    }
+
     pub fn deposit_funds(ctx: Context<DepositFunds>, task_id: u64, amount: u64) -> Result<()> {
         ctx.accounts.deposit_funds(task_id, amount, ctx.bumps)
     }
 
-    pub fn submit_response(ctx: Context<SubmitResponse>, cid: String) -> Result<()> {
-        ctx.accounts.submit_response(cid)
+    pub fn fund_bonus_pool(
+        ctx: Context<DepositFunds>,
+        task_id: u64,
+        amount: u64,
+        max_multiplier_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .fund_bonus_pool(task_id, amount, max_multiplier_bps, ctx.bumps)
+    }
+
+    pub fn set_min_trust_score(
+        ctx: Context<SetMinTrustScore>,
+        task_id: u64,
+        min_trust_score: u16,
+    ) -> Result<()> {
+        ctx.accounts.set_min_trust_score(task_id, min_trust_score)
+    }
+
+    pub fn set_task_allowlist(
+        ctx: Context<SetTaskAllowlist>,
+        task_id: u64,
+        allowlist_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.set_task_allowlist(task_id, allowlist_root)
+    }
+
+    pub fn set_commit_reveal(
+        ctx: Context<SetCommitReveal>,
+        task_id: u64,
+        reveal_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_commit_reveal(task_id, reveal_window)
+    }
+
+    pub fn set_dispute_window(
+        ctx: Context<SetDisputeWindow>,
+        task_id: u64,
+        dispute_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.set_dispute_window(task_id, dispute_window)
+    }
+
+    pub fn submit_response<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitResponse<'info>>,
+        cid: String,
+        proof: Vec<[u8; 32]>,
+        value: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .submit_response(cid, proof, value, ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn amend_response(ctx: Context<AmendResponse>, cid: String) -> Result<()> {
+        ctx.accounts.amend_response(cid)
+    }
+
+    pub fn attach_response_payload(
+        ctx: Context<AttachResponsePayload>,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.attach_response_payload(payload)
+    }
+
+    pub fn commit_response<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitResponse<'info>>,
+        commitment: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .commit_response(commitment, proof, ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn reveal_response(ctx: Context<RevealResponse>, cid: String, salt: [u8; 32]) -> Result<()> {
+        ctx.accounts.reveal_response(cid, salt)
+    }
+
+    pub fn forfeit_unrevealed_response(ctx: Context<ForfeitUnrevealedResponse>) -> Result<()> {
+        ctx.accounts.forfeit_unrevealed_response()
+    }
+
+    pub fn required_funding(ctx: Context<RequiredFunding>) -> Result<u64> {
+        ctx.accounts.required_funding()
+    }
+
+    pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+        ctx.accounts.reconcile_vault()
+    }
+
+    pub fn sponsor_task(ctx: Context<SponsorTask>, amount: u64) -> Result<()> {
+        ctx.accounts.sponsor_task(amount, ctx.bumps)
     }
 
     pub fn refund_remaining(ctx: Context<RefundRemaining>) -> Result<()> {
-        ctx.accounts.refund_remaining()
+        ctx.accounts.refund_remaining(ctx.bumps)
+    }
+
+    pub fn withdraw_surplus(ctx: Context<WithdrawSurplus>) -> Result<()> {
+        ctx.accounts.withdraw_surplus()
+    }
+
+    pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+        ctx.accounts.claim_sponsor_refund()
     }
 
     pub fn mark_task_complete(ctx: Context<MarkTaskComplete>) -> Result<()> {
         ctx.accounts.mark_task_complete()
     }
 
-    pub fn verify_response(ctx: Context<VerifyResponse>) -> Result<()> {
-        ctx.accounts.verify_response()
+    pub fn aggregate_responses<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AggregateResponses<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.aggregate_responses(ctx.remaining_accounts, ctx.bumps)
+    }
+
+    pub fn flag_outliers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlagOutliers<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.flag_outliers(ctx.remaining_accounts)
+    }
+
+    pub fn verify_response(ctx: Context<VerifyResponse>, reward_amount: Option<u64>) -> Result<()> {
+        ctx.accounts.verify_response(reward_amount)
+    }
+
+    pub fn reject_response(ctx: Context<RejectResponse>, reason: RejectionReason) -> Result<()> {
+        ctx.accounts.reject_response(reason)
+    }
+
+    pub fn add_to_denylist(
+        ctx: Context<AddToDenylist>,
+        responder: Pubkey,
+        reason: String,
+    ) -> Result<()> {
+        ctx.accounts.add_to_denylist(responder, reason, ctx.bumps)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>) -> Result<()> {
+        ctx.accounts.remove_from_denylist()
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>, bond: u64) -> Result<()> {
+        ctx.accounts.open_dispute(bond, ctx.bumps)
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, in_favor_of_challenger: bool) -> Result<()> {
+        ctx.accounts.resolve_dispute(in_favor_of_challenger)
+    }
+
+    pub fn register_juror(ctx: Context<RegisterJuror>, stake: u64) -> Result<()> {
+        ctx.accounts.register_juror(stake, ctx.bumps)
+    }
+
+    pub fn unregister_juror(ctx: Context<UnregisterJuror>) -> Result<()> {
+        ctx.accounts.unregister_juror()
+    }
+
+    pub fn withdraw_juror_stake(ctx: Context<WithdrawJurorStake>) -> Result<()> {
+        ctx.accounts.withdraw_juror_stake()
+    }
+
+    pub fn request_jury(ctx: Context<RequestJury>) -> Result<()> {
+        ctx.accounts.request_jury()
+    }
+
+    pub fn summon_jury(ctx: Context<SummonJury>) -> Result<()> {
+        ctx.accounts.summon_jury(ctx.bumps)
+    }
+
+    pub fn commit_vote(ctx: Context<CommitVote>, commitment: [u8; 32]) -> Result<()> {
+        ctx.accounts.commit_vote(commitment, ctx.bumps)
+    }
+
+    pub fn reveal_vote(ctx: Context<RevealVote>, vote_for_challenger: bool, salt: [u8; 32]) -> Result<()> {
+        ctx.accounts.reveal_vote(vote_for_challenger, salt)
+    }
+
+    pub fn resolve_jury_dispute(ctx: Context<ResolveJuryDispute>) -> Result<()> {
+        ctx.accounts.resolve_jury_dispute()
+    }
+
+    pub fn settle_juror(ctx: Context<SettleJuror>) -> Result<()> {
+        ctx.accounts.settle_juror()
     }
 
     // LEFT
     pub fn disburse_rewards(ctx: Context<DisburseRewards>) -> Result<()> {
         ctx.accounts.disburse_rewards()
     }
+
+    pub fn tip_responder(ctx: Context<TipResponder>, amount: u64) -> Result<()> {
+        ctx.accounts.tip_responder(amount)
+    }
+
+    pub fn set_referral_share(ctx: Context<SetReferralShare>, share_bps: u16) -> Result<()> {
+        ctx.accounts.set_referral_share(share_bps, ctx.bumps)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        ctx.accounts.register_referrer(ctx.bumps)
+    }
+
+    pub fn set_referrer(ctx: Context<SetReferrer>) -> Result<()> {
+        ctx.accounts.set_referrer(ctx.bumps)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        ctx.accounts.claim_referral_fees()
+    }
 }
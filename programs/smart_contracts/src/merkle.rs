@@ -0,0 +1,18 @@
+use anchor_lang::solana_program::hash::hashv;
+
+pub fn allowlist_leaf(responder: &[u8]) -> [u8; 32] {
+    hashv(&[responder]).to_bytes()
+}
+
+// Pairs are hashed in sorted order so proofs don't need to carry left/right flags
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+
+    computed == *root
+}
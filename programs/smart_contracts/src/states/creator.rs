@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    pub authority: Pubkey,
+    pub next_task_id: u64, // ID assigned by the next create_task_auto
+    pub tasks_created: u32,
+    pub lamports_escrowed: u64, // Net deposits, after platform fees
+    pub lamports_paid_out: u64, // Rewards disbursed to responders
+    pub lamports_tipped: u64,   // Tips paid to responders, after platform fees
+    pub lamports_refunded: u64,
+    pub fees_paid: u64,
+    pub cancellations: u32,
+    pub referrer: Option<Pubkey>, // Integrator that receives a share of this creator's fees
+    pub bump: u8,
+}
+
+// Statistics are informational and saturate instead of blocking the
+// instruction that updates them.
+impl CreatorProfile {
+    pub fn initialize(&mut self, authority: Pubkey, bump: u8) {
+        self.authority = authority;
+        self.next_task_id = 1;
+        self.bump = bump;
+    }
+
+    // A profile created in this instruction is still zeroed; task IDs start at 1
+    pub fn auto_task_id(&self) -> u64 {
+        self.next_task_id.max(1)
+    }
+
+    pub fn record_task_created(&mut self) {
+        self.tasks_created = self.tasks_created.saturating_add(1);
+    }
+
+    pub fn record_deposit(&mut self, net_deposit: u64, fee: u64) {
+        self.lamports_escrowed = self.lamports_escrowed.saturating_add(net_deposit);
+        self.fees_paid = self.fees_paid.saturating_add(fee);
+    }
+
+    pub fn record_payout(&mut self, amount: u64) {
+        self.lamports_paid_out = self.lamports_paid_out.saturating_add(amount);
+    }
+
+    pub fn record_tip(&mut self, net_tip: u64, fee: u64) {
+        self.lamports_tipped = self.lamports_tipped.saturating_add(net_tip);
+        self.fees_paid = self.fees_paid.saturating_add(fee);
+    }
+
+    pub fn record_refund(&mut self, refunded: u64) {
+        self.lamports_refunded = self.lamports_refunded.saturating_add(refunded);
+    }
+
+    pub fn record_cancellation(&mut self, refunded: u64) {
+        self.record_refund(refunded);
+        self.cancellations = self.cancellations.saturating_add(1);
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_DENYLIST_REASON_LEN;
+
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    pub responder: Pubkey,            // Blocked responder
+    pub added_by: Pubkey,             // Admin that added the entry
+    pub added_at: i64,                // Time the entry was added
+    pub bump: u8,                     // PDA bump
+    #[max_len(MAX_DENYLIST_REASON_LEN)]
+    pub reason: String,               // Why the responder was blocked
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct DisputeAccount {
+    pub task: Pubkey,                 // Task the disputed response belongs to
+    pub response: Pubkey,             // Disputed response
+    pub challenger: Pubkey,           // Party that opened the dispute
+    pub bond: u64,                    // Lamports posted by the challenger
+    pub opened_at: i64,               // Time the dispute was opened
+    pub resolved_at: i64,             // Time the dispute was resolved
+    pub status: DisputeStatus,        // Current outcome
+    pub has_jury: bool,               // Escalated to juror arbitration
+    pub jury_seed_slot: u64,          // Slot whose hash draws the jury, 0 until requested
+    pub bump: u8,                     // PDA bump
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    Open,
+    ResolvedForChallenger,
+    ResolvedForResponder,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{JURY_SIZE, MAX_JURORS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct JurorPool {
+    #[max_len(MAX_JURORS)]
+    pub jurors: Vec<Pubkey>,          // Registered juror authorities
+    #[max_len(MAX_JURORS)]
+    pub active_juries: Vec<u16>,      // Unresolved juries each juror sits on, by pool index
+    pub bump: u8,                     // PDA bump
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct JurorAccount {
+    pub authority: Pubkey,            // Juror wallet
+    pub stake: u64,                   // Lamports at risk
+    pub active_cases: u16,            // Committed votes not yet settled
+    pub unbonding_at: i64,            // Time the juror left the pool, 0 while active
+    pub bump: u8,                     // PDA bump
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct JuryAccount {
+    pub dispute: Pubkey,              // Dispute being arbitrated
+    #[max_len(JURY_SIZE)]
+    pub jurors: Vec<Pubkey>,          // Selected juror authorities
+    pub commit_deadline: i64,         // End of the commit phase
+    pub reveal_deadline: i64,         // End of the reveal phase
+    pub votes_for_challenger: u8,
+    pub votes_for_responder: u8,
+    pub reward_pool: u64,             // Share of the bond paid to majority jurors
+    pub is_resolved: bool,
+    pub in_favor_of_challenger: bool, // Outcome once resolved
+    pub settled_mask: u8,             // Bit per juror index once settled
+    pub bump: u8,                     // PDA bump
+}
+
+impl JuryAccount {
+    pub fn majority_votes(&self) -> u8 {
+        if self.in_favor_of_challenger {
+            self.votes_for_challenger
+        } else {
+            self.votes_for_responder
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct JurorVote {
+    pub juror: Pubkey,                // Juror authority
+    pub commitment: [u8; 32],         // hash(vote, salt, juror)
+    pub is_revealed: bool,
+    pub vote_for_challenger: bool,
+    pub bump: u8,                     // PDA bump
+}
@@ -1,11 +1,29 @@
 pub mod admin;
+pub mod creator;
+pub mod denylist;
+pub mod dispute;
+pub mod juror;
+pub mod protocol_stats;
+pub mod referral;
 pub mod responder;
+pub mod responder_task;
 pub mod response;
+pub mod sponsor;
 pub mod treasury;
 pub mod task;
+pub mod template;
 
 pub use admin::*;
+pub use creator::*;
+pub use denylist::*;
+pub use dispute::*;
+pub use juror::*;
+pub use protocol_stats::*;
+pub use referral::*;
 pub use responder::*;
+pub use responder_task::*;
 pub use response::*;
+pub use sponsor::*;
 pub use treasury::*;
 pub use task::*;
+pub use template::*;
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-wide counters, kept in a single PDA so clients can read them
+/// with one account fetch.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolStats {
+    pub total_tasks: u64,
+    pub open_tasks: u64,
+    pub total_responses: u64,
+    pub verified_responses: u64,
+    pub total_escrowed: u64, // Net deposits, after platform fees
+    pub total_disbursed: u64,
+    pub total_refunded: u64, // Escrow released back to creators and sponsors
+    pub total_tips: u64,     // Tips paid to responders, after platform fees
+    pub total_fees: u64,
+    pub bump: u8,
+}
+
+// Like creator profiles, statistics saturate instead of failing the
+// instruction that updates them.
+impl ProtocolStats {
+    pub fn record_task_opened(&mut self) {
+        self.total_tasks = self.total_tasks.saturating_add(1);
+        self.open_tasks = self.open_tasks.saturating_add(1);
+    }
+
+    pub fn record_task_closed(&mut self) {
+        self.open_tasks = self.open_tasks.saturating_sub(1);
+    }
+
+    pub fn record_response(&mut self) {
+        self.total_responses = self.total_responses.saturating_add(1);
+    }
+
+    pub fn record_verified(&mut self) {
+        self.verified_responses = self.verified_responses.saturating_add(1);
+    }
+
+    // A dispute overturned a verification
+    pub fn record_unverified(&mut self) {
+        self.verified_responses = self.verified_responses.saturating_sub(1);
+    }
+
+    pub fn record_deposit(&mut self, net_deposit: u64, fee: u64) {
+        self.total_escrowed = self.total_escrowed.saturating_add(net_deposit);
+        self.total_fees = self.total_fees.saturating_add(fee);
+    }
+
+    pub fn record_disbursement(&mut self, amount: u64) {
+        self.total_disbursed = self.total_disbursed.saturating_add(amount);
+    }
+
+    // Tips pass straight through the vault and are kept out of the escrow totals
+    pub fn record_tip(&mut self, net_tip: u64, fee: u64) {
+        self.total_tips = self.total_tips.saturating_add(net_tip);
+        self.total_fees = self.total_fees.saturating_add(fee);
+    }
+
+    pub fn record_refund(&mut self, amount: u64) {
+        self.total_refunded = self.total_refunded.saturating_add(amount);
+    }
+}
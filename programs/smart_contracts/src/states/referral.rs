@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// Share of the platform fee paid to referrers. Kept out of AdminAccount so
+// existing admin accounts keep their layout; a missing config means no share.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralConfig {
+    pub share_bps: u16,
+    pub bump: u8,
+}
+
+// Holds a referrer's share of platform fees until it is claimed
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerAccrual {
+    pub referrer: Pubkey,
+    pub unclaimed: u64,
+    pub total_accrued: u64,
+    pub bump: u8,
+}
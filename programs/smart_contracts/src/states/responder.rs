@@ -1,9 +1,60 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{TRUST_SCORE_INITIAL, TRUST_SCORE_MAX, TRUST_SCORE_WEIGHT_BPS};
+
 #[account]
 #[derive(InitSpace)]
 pub struct Responder {
   pub authority: Pubkey,
   pub rewards: u64,
   pub responder_bump: u8,
-}
\ No newline at end of file
+  pub responses_submitted: u32,
+  pub responses_accepted: u32,
+  pub responses_rejected: u32,
+  pub responses_disputed: u32,
+  pub responses_late: u32,
+  pub trust_score: u16,             // Decayed score in basis points
+  pub last_updated: i64,
+}
+
+impl Responder {
+  pub fn initialize(&mut self, authority: Pubkey, bump: u8) {
+    self.authority = authority;
+    self.responder_bump = bump;
+    self.trust_score = TRUST_SCORE_INITIAL;
+  }
+
+  pub fn record_submission(&mut self, timestamp: i64) {
+    self.responses_submitted = self.responses_submitted.saturating_add(1);
+    self.last_updated = timestamp;
+  }
+
+  pub fn record_accepted(&mut self, timestamp: i64) {
+    self.responses_accepted = self.responses_accepted.saturating_add(1);
+    self.apply_outcome(TRUST_SCORE_MAX, timestamp);
+  }
+
+  pub fn record_disputed(&mut self, timestamp: i64) {
+    self.responses_disputed = self.responses_disputed.saturating_add(1);
+    self.last_updated = timestamp;
+  }
+
+  pub fn record_rejected(&mut self, late: bool, timestamp: i64) {
+    self.responses_rejected = self.responses_rejected.saturating_add(1);
+    if late {
+      self.responses_late = self.responses_late.saturating_add(1);
+    }
+    self.apply_outcome(0, timestamp);
+  }
+
+  // Exponential moving average: the newest outcome gets TRUST_SCORE_WEIGHT_BPS,
+  // the previous score keeps the rest.
+  fn apply_outcome(&mut self, target: u16, timestamp: i64) {
+    let max = TRUST_SCORE_MAX as u64;
+    let score = (self.trust_score as u64 * (max - TRUST_SCORE_WEIGHT_BPS)
+      + target as u64 * TRUST_SCORE_WEIGHT_BPS)
+      / max;
+    self.trust_score = score.min(max) as u16;
+    self.last_updated = timestamp;
+  }
+}
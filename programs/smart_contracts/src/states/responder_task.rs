@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ResponderTaskState {
+    pub task: Pubkey,
+    pub responder: Pubkey,
+    pub submitted: u16, // Responses submitted so far, also the next response index
+    pub bump: u8,
+}
@@ -1,13 +1,37 @@
 use anchor_lang::prelude::*;
 
+use crate::cid::Cid;
+
 #[account]
 #[derive(InitSpace)]
 pub struct ResponseAccount {
     pub task_bump: u8,                // Linked task bump
     pub responder: Pubkey,            // Responder's pubkey
+    pub index: u16,                   // Sequence number among this responder's responses to the task
     pub timestamp: i64,               // Submission time
     pub is_verified: bool,            // Verification flag
+    pub is_rejected: bool,            // Rejection flag
+    pub is_disputed: bool,            // Payout frozen by an open dispute
+    pub dispute_settled: bool,        // Only one dispute per response, settling it ends the window
+    pub is_paid: bool,                // Reward already disbursed
+    pub owed: u64,                    // Locked reward, set to the graded amount on verification
+    pub tipped: u64,                  // Tips received from the task creator, net of fees
+    pub verified_at: i64,             // Verification time, starts the dispute window
+    pub commitment: Option<[u8; 32]>, // hash(cid, salt, responder) in commit-reveal mode
+    pub is_revealed: bool,            // CID revealed against the commitment
+    pub numeric_value: Option<i64>,   // Measurement for aggregated tasks
+    pub is_outlier: bool,             // Too far from the task aggregate
+    pub is_aggregated: bool,          // Counted by a batch of aggregate_responses
+    pub revision: u16,                // Times the CID was amended
+    pub cid_history: [u8; 32],        // hash(previous history, replaced digest) chain
     pub bump: u8,                     // PDA bump
-    #[max_len(100)]
-    pub cid: String,                  // IPFS or Arweave CID
+    pub cid: Option<Cid>,             // Content digest, None until a commitment is revealed
+    #[max_len(0)]
+    pub payload: Vec<u8>,             // Inline payload, account is reallocated to fit
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionReason {
+    Invalid,
+    Late,
 }
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorAccount {
+    pub task: Pubkey,
+    pub sponsor: Pubkey,
+    pub contributed: u64, // Net deposits, after platform fees
+    pub claimed: u64,     // Refunds claimed so far
+    pub bump: u8,
+}
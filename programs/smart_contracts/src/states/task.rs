@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    cid::Cid,
+    constants::{MAX_AGGREGATED_RESPONSES, MAX_PREREQUISITES, MAX_PREREQUISITE_OUTPUTS},
+};
+
 #[account]
 #[derive(InitSpace)]
 pub struct TaskAccount {
@@ -9,8 +14,61 @@ pub struct TaskAccount {
     pub max_responses: u16, // We decide
     pub deadline: i64,
     pub responses_received: u16, // Admin update
+    pub responses_paid: u16, // Rewards disbursed so far
     pub is_complete: bool,
+    pub is_cancelled: bool, // Refunded with responses still locked, closed to submissions
     pub task_bump: u8,
-    #[max_len(100)]
-    pub cid: String,
+    pub min_trust_score: u16, // 0 means no requirement
+    pub max_reward_multiplier_bps: u16, // 0 means no bonus pool
+    pub allowlist_root: Option<[u8; 32]>, // Merkle root of allowed responders
+    pub reveal_window: i64, // Commit-reveal mode when non-zero, reveals close at deadline + window
+    pub dispute_window: i64, // Seconds after verification before a response can be paid
+    pub aggregation: AggregationMethod,
+    pub outlier_tolerance: u64, // Max distance from the aggregate before a value is flagged, 0 disables
+    pub aggregate_result: Option<i64>,
+    pub max_responses_per_responder: u16,
+    #[max_len(MAX_PREREQUISITES)]
+    pub prerequisites: Vec<Pubkey>, // Tasks that must complete before submissions open
+    #[max_len(MAX_PREREQUISITE_OUTPUTS)]
+    pub prerequisite_outputs: Vec<Cid>, // Accepted prerequisite CIDs fed in as inputs
+    pub cid: Cid,
+    // Inline input, the account is reallocated to fit it (up to MAX_INPUT_SIZE)
+    #[max_len(0)]
+    pub input: Vec<u8>,
+}
+
+impl TaskAccount {
+    // Base rewards still owed if every unpaid response slot is eventually paid
+    pub fn outstanding_rewards(&self) -> Option<u64> {
+        let unpaid = self.max_responses.checked_sub(self.responses_paid)?;
+        self.reward_per_response.checked_mul(u64::from(unpaid))
+    }
+
+    // Base rewards of slots that can still receive a submission
+    pub fn open_slot_rewards(&self) -> Option<u64> {
+        if self.is_complete || self.is_cancelled {
+            return Some(0);
+        }
+        let open = self.max_responses.checked_sub(self.responses_received)?;
+        self.reward_per_response.checked_mul(u64::from(open))
+    }
+}
+
+// Values collected by batched aggregation until every response has been counted
+#[account]
+#[derive(InitSpace)]
+pub struct TaskAggregate {
+    pub task: Pubkey,
+    pub responses_counted: u16, // Aggregation finishes once this reaches responses_received
+    #[max_len(MAX_AGGREGATED_RESPONSES)]
+    pub values: Vec<i64>, // Values of verified, non-rejected responses
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AggregationMethod {
+    None,
+    Median,
+    Mean,
+    TrimmedMean,
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::cid::Cid;
+
+#[account]
+#[derive(InitSpace)]
+pub struct TaskTemplate {
+    pub template_id: u64,
+    pub creator: Pubkey,
+    pub reward_per_response: u64,
+    pub max_responses: u16,
+    pub first_start: i64,    // Earliest time the first epoch can open
+    pub interval: i64,       // Time between epoch start times
+    pub epoch_duration: i64, // Each epoch accepts responses this long after its scheduled start
+    pub total_epochs: u16,
+    pub epochs_opened: u16,
+    pub live_epochs: u16, // Opened epochs whose vault has not been closed
+    pub bump: u8,
+    pub cid: Cid,
+}
+
+impl TaskTemplate {
+    // Epoch tasks use the epoch number as their task ID, starting at 1
+    pub fn next_epoch(&self) -> u64 {
+        u64::from(self.epochs_opened) + 1
+    }
+
+    pub fn next_epoch_start(&self) -> Option<i64> {
+        self.interval
+            .checked_mul(i64::from(self.epochs_opened))
+            .and_then(|offset| self.first_start.checked_add(offset))
+    }
+
+    pub fn next_epoch_deadline(&self) -> Option<i64> {
+        self.next_epoch_start()?.checked_add(self.epoch_duration)
+    }
+
+    pub fn epoch_escrow(&self) -> Option<u64> {
+        self.reward_per_response
+            .checked_mul(u64::from(self.max_responses))
+    }
+}
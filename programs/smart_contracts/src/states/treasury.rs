@@ -5,6 +5,14 @@ use anchor_lang::prelude::*;
 pub struct RewardVault {
     pub task_bump: u8,                // Linked task bump
     pub balance: u64,                 // Vault balance
+    pub bonus_balance: u64,           // Reputation bonus pool
+    pub locked_balance: u64,          // Share of balance owed to submitted responses
+    pub deposited: u64,               // Net deposits into balance, creator and sponsors
+    pub sponsored: u64,               // Share of deposited from sponsors
+    pub sponsor_count: u32,           // SponsorAccounts not yet closed
+    pub sponsor_pool: u64,            // Released to sponsors, not yet claimed
+    pub sponsor_released: u64,        // Total ever released to sponsors
+    pub is_settled: bool,             // Nothing is locked and no more deposits are accepted
     pub bump: u8,                     // PDA bump
 }
 
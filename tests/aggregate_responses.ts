@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, generateAdminPDA, generateResponsePDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - aggregate_responses", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  // Submits and verifies one response per value, completing the task
+  const measuredTask = async (values: number[], aggregation: object, outlierTolerance: number) => {
+    const task = await createTask(wallet.publicKey, program, values.length);
+    await depositFunds(task.taskId, task.taskPDA, task.vaultPDA, task.rewardPerResponse.muln(values.length * 2), program);
+    await program.methods
+      .setTaskAggregation(task.taskId, aggregation as any, new anchor.BN(outlierTolerance))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    const responsePDAs: PublicKey[] = [];
+    for (const value of values) {
+      const responder = Keypair.generate();
+      await submitResponse(task.taskPDA, responder, toCid(`Reading${value}`), program, provider, [], new anchor.BN(value));
+      const [responsePDA] = generateResponsePDA(task.taskPDA, responder.publicKey, program);
+      await program.methods
+        .verifyResponse(null)
+        .accountsPartial({ taskAccount: task.taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+        .rpc();
+      responsePDAs.push(responsePDA);
+    }
+
+    return { ...task, responsePDAs };
+  };
+
+  const aggregate = (taskPDA: PublicKey, responsePDAs: PublicKey[]) =>
+    program.methods
+      .aggregateResponses()
+      .accountsPartial({ taskAccount: taskPDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .remainingAccounts(responsePDAs.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+
+  const flagOutliers = (taskPDA: PublicKey, responsePDAs: PublicKey[]) =>
+    program.methods
+      .flagOutliers()
+      .accountsPartial({ taskAccount: taskPDA })
+      .remainingAccounts(responsePDAs.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+      .rpc();
+
+  it("Stores the median and flags outliers", async () => {
+    const { taskPDA, responsePDAs } = await measuredTask([-61, -60, -58, -20], { median: {} }, 10);
+
+    await aggregate(taskPDA, responsePDAs);
+    await flagOutliers(taskPDA, responsePDAs);
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.aggregateResult.toNumber(), -59);
+
+    const outlier = await program.account.responseAccount.fetch(responsePDAs[3]);
+    assert.isTrue(outlier.isOutlier);
+    const inlier = await program.account.responseAccount.fetch(responsePDAs[0]);
+    assert.isFalse(inlier.isOutlier);
+  });
+
+  it("Computes the mean", async () => {
+    const { taskPDA, responsePDAs } = await measuredTask([10, 20, 30], { mean: {} }, 0);
+
+    await aggregate(taskPDA, responsePDAs);
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.aggregateResult.toNumber(), 20);
+  });
+
+  it("Aggregates in batches once every response is counted", async () => {
+    const { taskPDA, responsePDAs } = await measuredTask([1, 2, 3], { median: {} }, 0);
+
+    await aggregate(taskPDA, responsePDAs.slice(1));
+    let task = await program.account.taskAccount.fetch(taskPDA);
+    assert.isNull(task.aggregateResult);
+
+    // A response can only be counted once
+    try {
+      await aggregate(taskPDA, [responsePDAs[1]]);
+      assert.fail("Expected InvalidResponseAccounts");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidResponseAccounts");
+    }
+
+    await aggregate(taskPDA, [responsePDAs[0]]);
+    task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.aggregateResult.toNumber(), 2);
+  });
+
+  it("Fails to flag outliers before aggregation", async () => {
+    const { taskPDA, responsePDAs } = await measuredTask([1, 2, 3], { median: {} }, 1);
+
+    try {
+      await flagOutliers(taskPDA, responsePDAs);
+      assert.fail("Expected NotAggregated");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAggregated");
+    }
+  });
+
+  it("Fails submissions without a value on aggregated tasks", async () => {
+    const task = await createTask(wallet.publicKey, program, 3);
+    await depositFunds(task.taskId, task.taskPDA, task.vaultPDA, task.rewardPerResponse.muln(6), program);
+    await program.methods
+      .setTaskAggregation(task.taskId, { median: {} }, new anchor.BN(0))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    try {
+      await submitResponse(task.taskPDA, Keypair.generate(), toCid("NoValue"), program, provider);
+      assert.fail("Expected MissingNumericValue");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("MissingNumericValue");
+    }
+  });
+});
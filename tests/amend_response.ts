@@ -0,0 +1,71 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { cidDigest, createTask, depositFunds, generateAdminPDA, generateResponsePDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - amend_response", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const submittedResponse = async (label: string) => {
+    const task = await createTask(wallet.publicKey, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(task.maxResponses * 2)),
+      program
+    );
+
+    const responder = Keypair.generate();
+    await submitResponse(task.taskPDA, responder, toCid(label), program, provider);
+
+    const [responsePDA] = generateResponsePDA(task.taskPDA, responder.publicKey, program);
+    return { ...task, responsePDA };
+  };
+
+  const amend = (taskPDA: PublicKey, responsePDA: PublicKey, cid: string) =>
+    program.methods
+      .amendResponse(cid)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, admin: adminAuthority, adminAccount: adminAccountPDA })
+      .rpc();
+
+  it("Replaces the CID and chains the previous digest", async () => {
+    const { taskPDA, responsePDA } = await submittedResponse("WrongUpload");
+
+    await amend(taskPDA, responsePDA, toCid("FixedUpload"));
+
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    assert.equal(response.revision, 1);
+    assert.deepEqual(response.cid.digest, cidDigest("FixedUpload"));
+
+    const expectedHistory = createHash("sha256")
+      .update(Buffer.concat([Buffer.alloc(32), Buffer.from(cidDigest("WrongUpload"))]))
+      .digest();
+    assert.deepEqual(response.cidHistory, Array.from(expectedHistory));
+  });
+
+  it("Fails once the response is verified", async () => {
+    const { taskPDA, responsePDA } = await submittedResponse("Verified");
+
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    try {
+      await amend(taskPDA, responsePDA, toCid("TooLate"));
+      assert.fail("Expected ResponseAlreadyVerified");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponseAlreadyVerified");
+    }
+  });
+});
@@ -0,0 +1,287 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
+import { createHash, randomBytes } from "crypto";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, generateAdminPDA, generateResponsePDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - arbitration", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const jurors: Keypair[] = [];
+
+  const fund = async (keypair: Keypair) => {
+    const sig = await provider.connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+  };
+
+  const openDispute = async () => {
+    const task = await createTask(wallet.publicKey, program, 1);
+    await depositFunds(task.taskId, task.taskPDA, task.vaultPDA, task.rewardPerResponse.muln(2), program);
+
+    const responder = Keypair.generate();
+    await submitResponse(task.taskPDA, responder, toCid("Jury"), program, provider);
+    const [responsePDA] = generateResponsePDA(task.taskPDA, responder.publicKey, program);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: task.taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    const challenger = Keypair.generate();
+    await fund(challenger);
+    await program.methods
+      .openDispute(new anchor.BN(0.05 * LAMPORTS_PER_SOL))
+      .accountsPartial({ taskAccount: task.taskPDA, responseAccount: responsePDA, challenger: challenger.publicKey })
+      .signers([challenger])
+      .rpc();
+
+    const [disputePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), responsePDA.toBuffer()],
+      program.programId
+    );
+    const [juryPDA] = PublicKey.findProgramAddressSync([Buffer.from("jury"), disputePDA.toBuffer()], program.programId);
+
+    return { taskPDA: task.taskPDA, responder, responsePDA, challenger, disputePDA, juryPDA };
+  };
+
+  const requestJury = async (disputePDA: PublicKey, responsePDA: PublicKey) => {
+    await program.methods
+      .requestJury()
+      .accountsPartial({ disputeAccount: disputePDA, responseAccount: responsePDA, requester: wallet.publicKey })
+      .rpc();
+  };
+
+  // Jurors are drawn from a slot hash that only exists once the seed slot has passed
+  const summonJury = async (disputePDA: PublicKey, responsePDA: PublicKey) => {
+    await requestJury(disputePDA, responsePDA);
+    const { jurySeedSlot } = await program.account.disputeAccount.fetch(disputePDA);
+    while ((await provider.connection.getSlot()) <= jurySeedSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    await program.methods
+      .summonJury()
+      .accountsPartial({
+        disputeAccount: disputePDA,
+        responseAccount: responsePDA,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        payer: wallet.publicKey,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    for (let i = 0; i < 3; i++) {
+      const juror = Keypair.generate();
+      await fund(juror);
+      await program.methods
+        .registerJuror(new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+        .accounts({ authority: juror.publicKey })
+        .signers([juror])
+        .rpc();
+      jurors.push(juror);
+    }
+  });
+
+  it("Summons a jury and blocks admin resolution", async () => {
+    const { taskPDA, responder, responsePDA, challenger, disputePDA, juryPDA } = await openDispute();
+
+    await summonJury(disputePDA, responsePDA);
+
+    const jury = await program.account.juryAccount.fetch(juryPDA);
+    assert.equal(jury.jurors.length, 3);
+    assert.equal(new Set(jury.jurors.map((j) => j.toBase58())).size, 3);
+    // 20% of the bond is set aside for jurors
+    assert.equal(jury.rewardPool.toNumber(), 0.01 * LAMPORTS_PER_SOL);
+
+    try {
+      await program.methods
+        .resolveDispute(true)
+        .accountsPartial({
+          taskAccount: taskPDA,
+          disputeAccount: disputePDA,
+          responseAccount: responsePDA,
+          challenger: challenger.publicKey,
+          responder: responder.publicKey,
+          adminAccount: adminAccountPDA,
+          arbitrator: adminAuthority,
+        })
+        .rpc();
+      assert.fail("Expected JuryAssigned");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("JuryAssigned");
+    }
+  });
+
+  it("Accepts commitments only from selected jurors", async () => {
+    const { responsePDA, disputePDA, juryPDA } = await openDispute();
+
+    await summonJury(disputePDA, responsePDA);
+
+    const jury = await program.account.juryAccount.fetch(juryPDA);
+    const selected = jurors.find((j) => jury.jurors.some((k) => k.equals(j.publicKey)));
+
+    const salt = randomBytes(32);
+    const commitmentFor = (juror: PublicKey) =>
+      Array.from(createHash("sha256").update(Buffer.concat([Buffer.from([1]), salt, juror.toBuffer()])).digest());
+
+    // The pool is shared with earlier runs, so our jurors may not have been drawn
+    if (selected) {
+      await program.methods
+        .commitVote(commitmentFor(selected.publicKey))
+        .accountsPartial({ juryAccount: juryPDA, juror: selected.publicKey })
+        .signers([selected])
+        .rpc();
+
+      try {
+        await program.methods
+          .revealVote(true, Array.from(salt))
+          .accountsPartial({ juryAccount: juryPDA, juror: selected.publicKey })
+          .signers([selected])
+          .rpc();
+        assert.fail("Expected RevealPhaseNotOpen");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RevealPhaseNotOpen");
+      }
+    }
+
+    const outsider = Keypair.generate();
+    await fund(outsider);
+    try {
+      await program.methods
+        .commitVote(commitmentFor(outsider.publicKey))
+        .accountsPartial({ juryAccount: juryPDA, juror: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+      assert.fail("Expected the commit to fail");
+    } catch (err: any) {
+      expect(err.message).to.match(/NotSelectedJuror|AccountNotInitialized/);
+    }
+  });
+
+  it("Only lets the admin or the dispute parties request a jury", async () => {
+    const { responsePDA, disputePDA } = await openDispute();
+
+    const outsider = Keypair.generate();
+    await fund(outsider);
+    try {
+      await program.methods
+        .requestJury()
+        .accountsPartial({ disputeAccount: disputePDA, responseAccount: responsePDA, requester: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+      assert.fail("Expected Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("Draws the jury only after the seed slot and never from the parties", async () => {
+    const { responder, responsePDA, challenger, disputePDA, juryPDA } = await openDispute();
+
+    // Both parties join the pool, which must not let them judge their own dispute
+    for (const party of [challenger, responder]) {
+      await fund(party);
+      await program.methods
+        .registerJuror(new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+        .accounts({ authority: party.publicKey })
+        .signers([party])
+        .rpc();
+    }
+
+    await requestJury(disputePDA, responsePDA);
+    try {
+      await program.methods
+        .summonJury()
+        .accountsPartial({
+          disputeAccount: disputePDA,
+          responseAccount: responsePDA,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          payer: wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("Expected JurySeedPending");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("JurySeedPending");
+    }
+
+    const { jurySeedSlot } = await program.account.disputeAccount.fetch(disputePDA);
+    while ((await provider.connection.getSlot()) <= jurySeedSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+
+    // Anyone may complete the draw once the seed is fixed
+    const outsider = Keypair.generate();
+    await fund(outsider);
+    await program.methods
+      .summonJury()
+      .accountsPartial({
+        disputeAccount: disputePDA,
+        responseAccount: responsePDA,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        payer: outsider.publicKey,
+      })
+      .signers([outsider])
+      .rpc();
+
+    const jury = await program.account.juryAccount.fetch(juryPDA);
+    assert.isFalse(jury.jurors.some((k) => k.equals(challenger.publicKey) || k.equals(responder.publicKey)));
+  });
+
+  it("Keeps empanelled jurors in the pool until their jury is settled", async () => {
+    const { responsePDA, disputePDA, juryPDA } = await openDispute();
+
+    await summonJury(disputePDA, responsePDA);
+
+    const jury = await program.account.juryAccount.fetch(juryPDA);
+    const selected = jurors.find((j) => jury.jurors.some((k) => k.equals(j.publicKey)));
+
+    // The pool is shared with earlier runs, so our jurors may not have been drawn
+    if (selected) {
+      try {
+        await program.methods
+          .unregisterJuror()
+          .accounts({ authority: selected.publicKey })
+          .signers([selected])
+          .rpc();
+        assert.fail("Expected JurorHasActiveCases");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("JurorHasActiveCases");
+      }
+    }
+  });
+
+  it("Keeps unbonding jurors locked", async () => {
+    // A fresh juror has not been drawn onto any jury yet
+    const juror = Keypair.generate();
+    await fund(juror);
+    await program.methods
+      .registerJuror(new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+      .accounts({ authority: juror.publicKey })
+      .signers([juror])
+      .rpc();
+
+    await program.methods
+      .unregisterJuror()
+      .accounts({ authority: juror.publicKey })
+      .signers([juror])
+      .rpc();
+
+    try {
+      await program.methods
+        .withdrawJurorStake()
+        .accounts({ authority: juror.publicKey })
+        .signers([juror])
+        .rpc();
+      assert.fail("Expected UnbondingNotFinished");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("UnbondingNotFinished");
+    }
+  });
+});
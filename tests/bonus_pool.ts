@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  creatorProfileAccounts,
+  createTask,
+  depositFunds,
+  generateAdminPDA,
+  generateResponsePDA,
+  setDisputeWindow,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - fund_bonus_pool", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const responsePDAFor = (taskPDA: PublicKey, responder: Keypair) =>
+    generateResponsePDA(taskPDA, responder.publicKey, program)[0];
+
+  const verify = async (taskPDA: PublicKey, responder: Keypair) =>
+    program.methods
+      .verifyResponse(null)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: responsePDAFor(taskPDA, responder),
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+  const fundedTask = async (responses: number) => {
+    const task = await createTask(wallet.publicKey, program, responses);
+    await setDisputeWindow(task.taskId, 0, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(responses * 2)),
+      program
+    );
+    return task;
+  };
+
+  it("Pays a reputation bonus from the pool", async () => {
+    const responder = Keypair.generate();
+
+    // Build up reputation on an unrelated task first (score 5_000 -> 5_500)
+    const warmup = await fundedTask(2);
+    await submitResponse(warmup.taskPDA, responder, toCid("Warmup"), program, provider);
+    await verify(warmup.taskPDA, responder);
+
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await fundedTask(1);
+    await program.methods
+      .fundBonusPool(taskId, new anchor.BN(1_000_000), 15_000)
+      .accountsPartial({
+        creator: wallet.publicKey,
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+      })
+      .rpc();
+
+    await submitResponse(taskPDA, responder, toCid("Bonus"), program, provider);
+    await verify(taskPDA, responder);
+
+    const before = await provider.connection.getBalance(responder.publicKey);
+    await program.methods
+      .disburseRewards()
+      .accountsPartial({
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        responseAccount: responsePDAFor(taskPDA, responder),
+        recipient: responder.publicKey,
+        ...creatorProfileAccounts(wallet.publicKey, program),
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+    const after = await provider.connection.getBalance(responder.publicKey);
+
+    // 1.0x + 0.5x * (5_500 - 5_000) / 5_000 = 1.05x
+    const expectedBonus = rewardPerResponse.toNumber() * 0.05;
+    assert.equal(after - before, rewardPerResponse.toNumber() + expectedBonus);
+
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(vault.bonusBalance.toNumber(), 1_000_000 - 69_000 - expectedBonus);
+  });
+
+  it("Fails with a multiplier above the cap", async () => {
+    const { taskId, taskPDA, vaultPDA } = await fundedTask(1);
+
+    try {
+      await program.methods
+        .fundBonusPool(taskId, new anchor.BN(1_000_000), 15_001)
+        .accountsPartial({
+          creator: wallet.publicKey,
+          taskAccount: taskPDA,
+          rewardVault: vaultPDA,
+          adminAccount: adminAccountPDA,
+          adminAuthority,
+        })
+        .rpc();
+      assert.fail("Expected InvalidMultiplier");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidMultiplier");
+    }
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { createHash, randomBytes } from "crypto";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  createTaskAccounts,
+  depositFunds,
+  generateAdminPDA,
+  generateResponsePDA,
+  generateTaskPDA,
+  generateVaultPDA,
+  submitResponse,
+  cidDigest,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - commit_reveal", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const commitmentFor = (cid: string, salt: Buffer, responder: PublicKey) =>
+    Array.from(createHash("sha256").update(Buffer.concat([Buffer.from(cid), salt, responder.toBuffer()])).digest());
+
+  // Commit-reveal task whose submission deadline passes after `seconds`
+  const commitRevealTask = async (seconds: number) => {
+    const taskId = new anchor.BN(Math.floor(Math.random() * 1_000_000));
+    const rewardPerResponse = new anchor.BN(100_000);
+    const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + seconds);
+    const [taskPDA] = generateTaskPDA(wallet.publicKey, taskId, program);
+    const [vaultPDA] = generateVaultPDA(taskPDA, program);
+
+    await program.methods
+      .createTask(taskId, rewardPerResponse, 5, deadline, toCid("CommitReveal"))
+      .accountsPartial(await createTaskAccounts(wallet.publicKey, program))
+      .rpc();
+    await depositFunds(taskId, taskPDA, vaultPDA, new anchor.BN(0.01 * LAMPORTS_PER_SOL), program);
+    await program.methods
+      .setCommitReveal(taskId, new anchor.BN(600))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    return { taskId, taskPDA };
+  };
+
+  const commit = async (taskPDA: PublicKey, responder: Keypair, commitment: number[]) => {
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    await program.methods
+      .commitResponse(commitment, [])
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: responsePDA,
+        responder: responder.publicKey,
+        admin: adminAuthority,
+        adminAccount: adminAccountPDA,
+      })
+      .rpc();
+    return responsePDA;
+  };
+
+  it("Reveals a committed CID after the deadline", async () => {
+    const { taskPDA } = await commitRevealTask(4);
+    const responder = Keypair.generate();
+    const salt = randomBytes(32);
+    const responsePDA = await commit(taskPDA, responder, commitmentFor(toCid("Secret"), salt, responder.publicKey));
+
+    let response = await program.account.responseAccount.fetch(responsePDA);
+    assert.isNull(response.cid);
+
+    try {
+      await program.methods
+        .verifyResponse(null)
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+        .rpc();
+      assert.fail("Expected ResponseNotRevealed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponseNotRevealed");
+    }
+
+    await new Promise((r) => setTimeout(r, 5000));
+
+    try {
+      await program.methods
+        .revealResponse(toCid("Copied"), Array.from(salt))
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, admin: adminAuthority })
+        .rpc();
+      assert.fail("Expected ResponseCommitmentMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponseCommitmentMismatch");
+    }
+
+    await program.methods
+      .revealResponse(toCid("Secret"), Array.from(salt))
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, admin: adminAuthority })
+      .rpc();
+
+    response = await program.account.responseAccount.fetch(responsePDA);
+    assert.deepEqual(response.cid.digest, cidDigest("Secret"));
+    assert.isTrue(response.isRevealed);
+
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+  });
+
+  it("Fails to reveal before the deadline", async () => {
+    const { taskPDA } = await commitRevealTask(3600);
+    const responder = Keypair.generate();
+    const salt = randomBytes(32);
+    const responsePDA = await commit(taskPDA, responder, commitmentFor(toCid("Early"), salt, responder.publicKey));
+
+    try {
+      await program.methods
+        .revealResponse(toCid("Early"), Array.from(salt))
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, admin: adminAuthority })
+        .rpc();
+      assert.fail("Expected RevealWindowNotOpen");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("RevealWindowNotOpen");
+    }
+  });
+
+  it("Fails plain submissions on commit-reveal tasks", async () => {
+    const { taskPDA } = await commitRevealTask(3600);
+
+    try {
+      await submitResponse(taskPDA, Keypair.generate(), toCid("Plain"), program, provider);
+      assert.fail("Expected CommitRevealRequired");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("CommitRevealRequired");
+    }
+  });
+});
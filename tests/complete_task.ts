@@ -5,9 +5,10 @@ import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
 import {
   createTask,
-  depositFunds,
+  fundTask,
   generateAdminPDA,
-  submitResponse
+  submitResponse,
+  toCid,
 } from "./test-utils";
 
 describe("nodara - mark_task_complete", () => {
@@ -20,16 +21,15 @@ describe("nodara - mark_task_complete", () => {
   const [adminAccountPDA] = generateAdminPDA(program);
 
   it("Marks task as complete after responses received", async () => {
-    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program, 3);
-    const totalReward = rewardPerResponse.mul(new anchor.BN(maxResponses));
-    await depositFunds(taskId, taskPDA, vaultPDA, totalReward, program);
+    const { taskId, taskPDA, vaultPDA, maxResponses } = await createTask(wallet.publicKey, program, 3);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
 
     // Submit responses from N responders
     const responders: Keypair[] = [];
     for (let i = 0; i < maxResponses; i++) {
       const responder = Keypair.generate();
       responders.push(responder);
-      await submitResponse(taskPDA, responder, `QmRes${i}`, program, provider);
+      await submitResponse(taskPDA, responder, toCid(`Res${i}`), program, provider);
     }
 
     const task = await program.account.taskAccount.fetch(taskPDA);
@@ -37,14 +37,13 @@ describe("nodara - mark_task_complete", () => {
   });
 
   it("Fails if already marked complete", async () => {
-    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program);
-    const totalReward = rewardPerResponse.mul(new anchor.BN(maxResponses));
-    await depositFunds(taskId, taskPDA, vaultPDA, totalReward, program);
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
 
     const responder1 = Keypair.generate();
     const responder2 = Keypair.generate();
-    await submitResponse(taskPDA, responder1, "QmOne", program, provider);
-    await submitResponse(taskPDA, responder2, "QmTwo", program, provider);
+    await submitResponse(taskPDA, responder1, toCid("One"), program, provider);
+    await submitResponse(taskPDA, responder2, toCid("Two"), program, provider);
 
     await program.methods
       .refundRemaining()
@@ -79,14 +78,13 @@ describe("nodara - mark_task_complete", () => {
   });
 
   it("fails if non-admin or non-creator tries to complete", async () => {
-    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program);
-    const totalReward = rewardPerResponse.mul(new anchor.BN(maxResponses));
-    await depositFunds(taskId, taskPDA, vaultPDA, totalReward, program);
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
 
     const responder1 = Keypair.generate();
     const responder2 = Keypair.generate();
-    await submitResponse(taskPDA, responder1, "QmA", program, provider);
-    await submitResponse(taskPDA, responder2, "QmB", program, provider);
+    await submitResponse(taskPDA, responder1, toCid("A"), program, provider);
+    await submitResponse(taskPDA, responder2, toCid("B"), program, provider);
 
     const intruder = Keypair.generate();
     const sig = await provider.connection.requestAirdrop(intruder.publicKey, LAMPORTS_PER_SOL);
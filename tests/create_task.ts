@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
-import { validTaskInput } from "./test-utils";
+import { cidDigest, toCid, validTaskInput } from "./test-utils";
 
 describe("nodara - create_task", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -10,15 +10,16 @@ describe("nodara - create_task", () => {
   const provider = anchor.getProvider();
   const wallet = provider.wallet;
 
+  // Clients built before creator profiles and protocol stats pass neither
+  const legacyAccounts = { creator: wallet.publicKey, creatorProfile: null, protocolStats: null };
+
   it("Creates a task successfully", async () => {
     const { taskId, rewardPerResponse, maxResponses, deadline, cid, taskPDA } =
       await validTaskInput(wallet.publicKey, program);
 
     await program.methods
       .createTask(taskId, rewardPerResponse, maxResponses, deadline, cid)
-      .accounts({
-        creator: wallet.publicKey,
-      })
+      .accountsPartial(legacyAccounts)
       .rpc();
 
     const task = await program.account.taskAccount.fetch(taskPDA);
@@ -33,7 +34,8 @@ describe("nodara - create_task", () => {
     assert.strictEqual(task.responsesReceived, 0);
     assert.strictEqual(task.isComplete, false);
     assert.strictEqual(task.deadline.toNumber(), deadline.toNumber());
-    assert.strictEqual(task.cid, cid);
+    assert.deepEqual(task.cid.digest, cidDigest("ValidCIDHash"));
+    assert.strictEqual(task.cid.version, 1);
   });
 
   it("Fails with task_id = 0", async () => {
@@ -45,9 +47,7 @@ describe("nodara - create_task", () => {
     try {
       await program.methods
         .createTask(taskId, rewardPerResponse, maxResponses, deadline, cid)
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
 
       expect.fail("Expected createTask to throw an error but it succeeded.");
@@ -63,9 +63,7 @@ describe("nodara - create_task", () => {
     try {
       await program.methods
         .createTask(taskId, new anchor.BN(0), maxResponses, deadline, cid)
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
       expect.fail("Expected to throw InvalidReward but it succeeded.");
     } catch (err) {
@@ -80,9 +78,7 @@ describe("nodara - create_task", () => {
     try {
       await program.methods
         .createTask(taskId, rewardPerResponse, 0, deadline, cid)
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
       expect.fail("Expected to throw InvalidMaxResponses but it succeeded.");
     } catch (err) {
@@ -99,9 +95,7 @@ describe("nodara - create_task", () => {
     try {
       await program.methods
         .createTask(taskId, rewardPerResponse, maxResponses, pastDeadline, cid)
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
       expect.fail("Expected to throw InvalidDeadline but it succeeded.");
     } catch (err) {
@@ -116,9 +110,7 @@ describe("nodara - create_task", () => {
     try {
       await program.methods
         .createTask(taskId, rewardPerResponse, maxResponses, deadline, "")
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
       expect.fail("Expected to throw InvalidCID but it succeeded.");
     } catch (err) {
@@ -126,6 +118,36 @@ describe("nodara - create_task", () => {
     }
   });
 
+  it("Fails with a truncated CID", async () => {
+    const { taskId, rewardPerResponse, maxResponses, deadline } =
+      await validTaskInput(wallet.publicKey, program);
+
+    try {
+      await program.methods
+        .createTask(taskId, rewardPerResponse, maxResponses, deadline, toCid("Truncated").slice(0, -8))
+        .accountsPartial(legacyAccounts)
+        .rpc();
+      expect.fail("Expected to throw InvalidCidEncoding but it succeeded.");
+    } catch (err) {
+      expect(err.message).to.match(/InvalidCidEncoding/);
+    }
+  });
+
+  it("Fails with an unsupported multibase", async () => {
+    const { taskId, rewardPerResponse, maxResponses, deadline } =
+      await validTaskInput(wallet.publicKey, program);
+
+    try {
+      await program.methods
+        .createTask(taskId, rewardPerResponse, maxResponses, deadline, "QmValidCIDHash")
+        .accountsPartial(legacyAccounts)
+        .rpc();
+      expect.fail("Expected to throw UnsupportedMultibase but it succeeded.");
+    } catch (err) {
+      expect(err.message).to.match(/UnsupportedMultibase/);
+    }
+  });
+
   it("Fails when task account already exists", async () => {
     const input = await validTaskInput(wallet.publicKey, program);
 
@@ -138,9 +160,7 @@ describe("nodara - create_task", () => {
         input.deadline,
         input.cid
       )
-      .accounts({
-        creator: wallet.publicKey,
-      })
+      .accountsPartial(legacyAccounts)
       .rpc();
 
     // Re-creating same task → should fail
@@ -153,9 +173,7 @@ describe("nodara - create_task", () => {
           input.deadline,
           input.cid
         )
-        .accounts({
-          creator: wallet.publicKey,
-        })
+        .accountsPartial(legacyAccounts)
         .rpc();
       expect.fail(
         "Expected failure due to duplicate task account, but it succeeded."
@@ -0,0 +1,77 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTaskAccounts, generateCreatorProfilePDA, generateTaskPDA, toCid } from "./test-utils";
+
+describe("nodara - create_task_auto", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+
+  const [profilePDA] = generateCreatorProfilePDA(wallet.publicKey, program);
+
+  // Other suites share the wallet, so start from whatever the profile holds
+  const nextTaskId = async () => {
+    const profile = await program.account.creatorProfile.fetchNullable(profilePDA);
+    return profile ? profile.nextTaskId.toNumber() : 1;
+  };
+
+  const createAuto = (cid: string, maxResponses = 5) => {
+    const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+    return program.methods
+      .createTaskAuto(new anchor.BN(100_000), maxResponses, deadline, cid)
+      .accountsPartial({ creator: wallet.publicKey })
+      .rpc();
+  };
+
+  it("Assigns sequential task IDs per creator", async () => {
+    const firstId = await nextTaskId();
+
+    await createAuto(toCid("AutoFirst"));
+    await createAuto(toCid("AutoSecond"));
+
+    const [firstPDA] = generateTaskPDA(wallet.publicKey, new anchor.BN(firstId), program);
+    const [secondPDA] = generateTaskPDA(wallet.publicKey, new anchor.BN(firstId + 1), program);
+
+    const first = await program.account.taskAccount.fetch(firstPDA);
+    const second = await program.account.taskAccount.fetch(secondPDA);
+    assert.equal(first.taskId.toNumber(), firstId);
+    assert.equal(second.taskId.toNumber(), firstId + 1);
+
+    const profile = await program.account.creatorProfile.fetch(profilePDA);
+    assert.equal(profile.nextTaskId.toNumber(), firstId + 2);
+    assert.equal(profile.authority.toBase58(), wallet.publicKey.toBase58());
+  });
+
+  it("Does not consume an ID when validation fails", async () => {
+    const before = await nextTaskId();
+
+    try {
+      await createAuto(toCid("AutoInvalid"), 0);
+      assert.fail("Expected InvalidMaxResponses");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidMaxResponses");
+    }
+
+    assert.equal(await nextTaskId(), before);
+  });
+
+  it("Skips IDs taken by explicitly created tasks", async () => {
+    const explicitId = new anchor.BN((await nextTaskId()) + 5);
+    const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    await program.methods
+      .createTask(explicitId, new anchor.BN(100_000), 5, deadline, toCid("AutoExplicit"))
+      .accountsPartial(await createTaskAccounts(wallet.publicKey, program))
+      .rpc();
+    assert.equal(await nextTaskId(), explicitId.toNumber() + 1);
+
+    await createAuto(toCid("AutoAfterExplicit"));
+
+    const [autoPDA] = generateTaskPDA(wallet.publicKey, explicitId.addn(1), program);
+    const task = await program.account.taskAccount.fetch(autoPDA);
+    assert.equal(task.taskId.toNumber(), explicitId.toNumber() + 1);
+  });
+});
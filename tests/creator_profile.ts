@@ -0,0 +1,109 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  creatorProfileAccounts,
+  createTask,
+  depositFunds,
+  generateAdminPDA,
+  generateCreatorProfilePDA,
+  generateResponsePDA,
+  setDisputeWindow,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - creator_profile", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const [profilePDA] = generateCreatorProfilePDA(wallet.publicKey, program);
+
+  // Other suites share the wallet, so assert on deltas
+  const fetchProfile = () => program.account.creatorProfile.fetch(profilePDA);
+
+  it("Tracks created tasks, escrow, fees and cancellations", async () => {
+    // Only the auto-ID instruction creates the profile
+    await program.methods
+      .createTaskAuto(new anchor.BN(100_000), 5, new anchor.BN(Math.floor(Date.now() / 1000) + 3600), toCid("Profile"))
+      .accountsPartial({ creator: wallet.publicKey })
+      .rpc();
+    const before = await fetchProfile();
+
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    const deposit = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    await depositFunds(taskId, taskPDA, vaultPDA, deposit, program);
+
+    const fee = deposit.muln(69).divn(1000);
+    const net = deposit.sub(fee);
+
+    await program.methods
+      .refundRemaining()
+      .accountsPartial({ creator: wallet.publicKey, taskAccount: taskPDA, rewardVault: vaultPDA })
+      .rpc();
+
+    const after = await fetchProfile();
+    assert.equal(after.tasksCreated - before.tasksCreated, 1);
+    assert.equal(after.lamportsEscrowed.sub(before.lamportsEscrowed).toString(), net.toString());
+    assert.equal(after.feesPaid.sub(before.feesPaid).toString(), fee.toString());
+    assert.equal(after.lamportsRefunded.sub(before.lamportsRefunded).toString(), net.toString());
+    assert.equal(after.cancellations - before.cancellations, 1);
+  });
+
+  it("Tracks rewards paid out", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await createTask(wallet.publicKey, program, 1);
+    await setDisputeWindow(taskId, 0, program);
+    await depositFunds(taskId, taskPDA, vaultPDA, rewardPerResponse.muln(2), program);
+
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("ProfilePayout"), program, provider);
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    // The profile cannot be swapped out to keep the payout off the creator's stats
+    try {
+      await program.methods
+        .disburseRewards()
+        .accountsPartial({
+          taskAccount: taskPDA,
+          rewardVault: vaultPDA,
+          responseAccount: responsePDA,
+          recipient: responder.publicKey,
+          taskCreator: wallet.publicKey,
+          creatorProfile: generateCreatorProfilePDA(responder.publicKey, program)[0],
+          adminAccount: adminAccountPDA,
+          signer: adminAuthority,
+        })
+        .rpc();
+      assert.fail("Expected InvalidCreatorProfile");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidCreatorProfile");
+    }
+
+    const before = await fetchProfile();
+    await program.methods
+      .disburseRewards()
+      .accountsPartial({
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        responseAccount: responsePDA,
+        recipient: responder.publicKey,
+        ...creatorProfileAccounts(wallet.publicKey, program),
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+    const after = await fetchProfile();
+    assert.equal(after.lamportsPaidOut.sub(before.lamportsPaidOut).toString(), rewardPerResponse.toString());
+  });
+});
@@ -0,0 +1,90 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, generateAdminPDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - denylist", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const generateDenylistPDA = (responder: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("denylist"), responder.toBuffer()], program.programId);
+
+  const fundedTask = async () => {
+    const task = await createTask(wallet.publicKey, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(task.maxResponses * 2)),
+      program
+    );
+    return task;
+  };
+
+  it("Blocks a denylisted responder until removed", async () => {
+    const responder = Keypair.generate();
+    const [denylistPDA] = generateDenylistPDA(responder.publicKey);
+
+    await program.methods
+      .addToDenylist(responder.publicKey, "Spoofed GPS readings")
+      .accountsPartial({ adminAccount: adminAccountPDA, admin: wallet.publicKey })
+      .rpc();
+
+    const entry = await program.account.denylistEntry.fetch(denylistPDA);
+    assert.equal(entry.reason, "Spoofed GPS readings");
+    assert.equal(entry.addedBy.toBase58(), wallet.publicKey.toBase58());
+
+    const { taskPDA } = await fundedTask();
+    try {
+      await submitResponse(taskPDA, responder, toCid("Blocked"), program, provider);
+      assert.fail("Expected ResponderDenylisted");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponderDenylisted");
+    }
+
+    await program.methods
+      .removeFromDenylist()
+      .accountsPartial({ denylistEntry: denylistPDA, adminAccount: adminAccountPDA, admin: wallet.publicKey })
+      .rpc();
+
+    await submitResponse(taskPDA, responder, toCid("Unblocked"), program, provider);
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.responsesReceived, 1);
+  });
+
+  it("Fails if non-admin tries to add an entry", async () => {
+    const intruder = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(intruder.publicKey, 0.1 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .addToDenylist(Keypair.generate().publicKey, "No reason")
+        .accountsPartial({ adminAccount: adminAccountPDA, admin: intruder.publicKey })
+        .signers([intruder])
+        .rpc();
+      assert.fail("Expected Unauthorized");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("Fails with a reason that is too long", async () => {
+    try {
+      await program.methods
+        .addToDenylist(Keypair.generate().publicKey, "x".repeat(101))
+        .accountsPartial({ adminAccount: adminAccountPDA, admin: wallet.publicKey })
+        .rpc();
+      assert.fail("Expected ReasonTooLong");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ReasonTooLong");
+    }
+  });
+});
@@ -166,4 +166,28 @@ describe("nodara - deposit_funds", () => {
       expect(err.message).to.include("Account `adminAuthority` not provided.");
     }
   });
+
+  it("Reports the exact deposit that opens a task", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program);
+    const requiredFunding = () =>
+      program.methods.requiredFunding().accounts({ taskAccount: taskPDA }).view();
+
+    // The program rounds the fee down, so compute the net exactly
+    const netOf = (amount: anchor.BN) => amount.sub(amount.muln(69).divn(1000));
+
+    const required = await requiredFunding();
+    const liability = rewardPerResponse.muln(maxResponses);
+    assert.isTrue(netOf(required).gte(liability));
+    assert.isTrue(netOf(required.subn(1)).lt(liability));
+
+    await program.methods.depositFunds(taskId, required).accountsPartial({
+      creator: wallet.publicKey,
+      taskAccount: taskPDA,
+      rewardVault: vaultPDA,
+      adminAccount: adminAccountPDA,
+      adminAuthority,
+    }).rpc();
+
+    assert.equal((await requiredFunding()).toNumber(), 0);
+  });
 });
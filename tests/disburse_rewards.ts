@@ -4,10 +4,16 @@ import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
 import {
+  creatorProfileAccounts,
   createTask,
   depositFunds,
+  fundTask,
   generateAdminPDA,
+  generateCreatorProfilePDA,
+  generateResponsePDA,
   generateVaultPDA,
+  setDisputeWindow,
+  toCid,
 } from "./test-utils";
 
 describe("nodara - disburse_rewards", () => {
@@ -30,6 +36,7 @@ describe("nodara - disburse_rewards", () => {
       program,
       numResponses
     );
+    await setDisputeWindow(taskId, 0, program);
 
     // Deposit enough funds for all responses
     const totalDeposit = rewardPerResponse.mul(new anchor.BN(numResponses));
@@ -44,14 +51,7 @@ describe("nodara - disburse_rewards", () => {
       responders.push(responder);
 
       // Generate response PDA
-      const [responsePDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("response"),
-          taskPDA.toBuffer(),
-          responder.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+      const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
       responsePDAs.push(responsePDA);
 
       // Airdrop to responder
@@ -63,7 +63,7 @@ describe("nodara - disburse_rewards", () => {
 
       // Submit response
       await program.methods
-        .submitResponse(`QmResponse${i}`)
+        .submitResponse(toCid(`Response${i}`), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
           responseAccount: responsePDA,
@@ -75,8 +75,9 @@ describe("nodara - disburse_rewards", () => {
 
       // Verify response
       await program.methods
-        .verifyResponse()
+        .verifyResponse(null)
         .accountsPartial({
+          taskAccount: taskPDA,
           responseAccount: responsePDA,
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
@@ -111,18 +112,12 @@ describe("nodara - disburse_rewards", () => {
       program,
       1
     );
+    await setDisputeWindow(taskId, 0, program);
 
-    await depositFunds(taskId, taskPDA, vaultPDA, rewardPerResponse, program);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
 
     const responder = Keypair.generate();
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("response"),
-        taskPDA.toBuffer(),
-        responder.publicKey.toBuffer(),
-      ],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
     // Airdrop and submit response (but don't verify)
     const sig = await provider.connection.requestAirdrop(
@@ -132,7 +127,7 @@ describe("nodara - disburse_rewards", () => {
     await provider.connection.confirmTransaction(sig);
 
     await program.methods
-      .submitResponse("QmUnverified")
+      .submitResponse(toCid("Unverified"), [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA,
@@ -179,6 +174,7 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDA,
           recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
@@ -229,6 +225,7 @@ describe("nodara - disburse_rewards", () => {
             rewardVault: vaultPDA,
             responseAccount: responsePDA,
             recipient: responder.publicKey,
+            ...creatorProfileAccounts(wallet.publicKey, program),
             adminAccount: adminAccountPDA,
             signer: adminAuthority,
           })
@@ -264,14 +261,9 @@ describe("nodara - disburse_rewards", () => {
         program,
         2 // Max 2 responses for auto-completion
       );
+      await setDisputeWindow(taskId, 0, program);
 
-      await depositFunds(
-        taskId,
-        taskPDA,
-        vaultPDA,
-        rewardPerResponse.mul(new anchor.BN(2)),
-        program
-      );
+      await fundTask(taskId, taskPDA, vaultPDA, program);
 
       // Submit exactly max responses to trigger auto-completion
       const responders = [];
@@ -281,14 +273,7 @@ describe("nodara - disburse_rewards", () => {
         const responder = Keypair.generate();
         responders.push(responder);
 
-        const [responsePDA] = PublicKey.findProgramAddressSync(
-          [
-            Buffer.from("response"),
-            taskPDA.toBuffer(),
-            responder.publicKey.toBuffer(),
-          ],
-          program.programId
-        );
+        const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
         responsePDAs.push(responsePDA);
 
         const sig = await provider.connection.requestAirdrop(
@@ -298,7 +283,7 @@ describe("nodara - disburse_rewards", () => {
         await provider.connection.confirmTransaction(sig);
 
         await program.methods
-          .submitResponse(`QmAuto${i}`)
+          .submitResponse(toCid(`Auto${i}`), [], null)
           .accountsPartial({
             taskAccount: taskPDA,
             responseAccount: responsePDA,
@@ -309,8 +294,9 @@ describe("nodara - disburse_rewards", () => {
           .rpc();
 
         await program.methods
-          .verifyResponse()
+          .verifyResponse(null)
           .accountsPartial({
+            taskAccount: taskPDA,
             responseAccount: responsePDA,
             adminAccount: adminAccountPDA,
             signer: adminAuthority,
@@ -330,11 +316,82 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDAs[0],
           recipient: responders[0].publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
         .rpc();
     });
+
+    it("Pays only the amount assigned at verification", async () => {
+      const { taskPDA, vaultPDA, responder, responsePDA, rewardPerResponse } =
+        await createTaskWithUnverifiedResponse();
+      const graded = rewardPerResponse.divn(2);
+
+      await program.methods
+        .verifyResponse(graded)
+        .accountsPartial({
+          taskAccount: taskPDA,
+          responseAccount: responsePDA,
+          adminAccount: adminAccountPDA,
+          signer: adminAuthority,
+        })
+        .rpc();
+
+      const initialRecipientBalance = await provider.connection.getBalance(
+        responder.publicKey
+      );
+      const initialVault = await program.account.rewardVault.fetch(vaultPDA);
+
+      await program.methods
+        .disburseRewards()
+        .accountsPartial({
+          taskAccount: taskPDA,
+          rewardVault: vaultPDA,
+          responseAccount: responsePDA,
+          recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
+          adminAccount: adminAccountPDA,
+          signer: adminAuthority,
+        })
+        .rpc();
+
+      const finalRecipientBalance = await provider.connection.getBalance(
+        responder.publicKey
+      );
+      const finalVault = await program.account.rewardVault.fetch(vaultPDA);
+
+      assert.equal(
+        finalRecipientBalance - initialRecipientBalance,
+        graded.toNumber(),
+        "Recipient should receive the graded amount"
+      );
+      assert.equal(
+        initialVault.balance.toNumber() - finalVault.balance.toNumber(),
+        graded.toNumber(),
+        "The ungraded remainder stays in the vault"
+      );
+      assert.equal(finalVault.lockedBalance.toNumber(), 0);
+
+      // The creator takes back the ungraded remainder, closing the vault
+      const creatorBalance = await provider.connection.getBalance(wallet.publicKey);
+      await program.methods
+        .withdrawSurplus()
+        .accountsPartial({
+          taskAccount: taskPDA,
+          rewardVault: vaultPDA,
+          creator: wallet.publicKey,
+          creatorProfile: generateCreatorProfilePDA(wallet.publicKey, program)[0],
+          signer: wallet.publicKey,
+        })
+        .rpc();
+
+      assert.isNull(await provider.connection.getAccountInfo(vaultPDA));
+      assert.isAbove(
+        (await provider.connection.getBalance(wallet.publicKey)) - creatorBalance,
+        finalVault.balance.toNumber()
+      );
+    });
   });
 
   describe("Error Cases", () => {
@@ -350,6 +407,7 @@ describe("nodara - disburse_rewards", () => {
             rewardVault: vaultPDA,
             responseAccount: responsePDA,
             recipient: responder.publicKey,
+            ...creatorProfileAccounts(wallet.publicKey, program),
             adminAccount: adminAccountPDA,
             signer: adminAuthority,
           })
@@ -362,23 +420,16 @@ describe("nodara - disburse_rewards", () => {
     });
 
     it("Fails if task is not complete", async () => {
-      const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await createTask(
+      const { taskId, taskPDA, vaultPDA } = await createTask(
         wallet.publicKey,
         program,
         2
       );
 
-      await depositFunds(taskId, taskPDA, vaultPDA, rewardPerResponse, program);
+      await fundTask(taskId, taskPDA, vaultPDA, program);
 
       const responder = Keypair.generate();
-      const [responsePDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("response"),
-          taskPDA.toBuffer(),
-          responder.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+      const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
       const sig = await provider.connection.requestAirdrop(
         responder.publicKey,
@@ -387,7 +438,7 @@ describe("nodara - disburse_rewards", () => {
       await provider.connection.confirmTransaction(sig);
 
       await program.methods
-        .submitResponse("QmIncomplete")
+        .submitResponse(toCid("Incomplete"), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
           responseAccount: responsePDA,
@@ -398,8 +449,9 @@ describe("nodara - disburse_rewards", () => {
         .rpc();
 
       await program.methods
-        .verifyResponse()
+        .verifyResponse(null)
         .accountsPartial({
+          taskAccount: taskPDA,
           responseAccount: responsePDA,
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
@@ -415,6 +467,7 @@ describe("nodara - disburse_rewards", () => {
             rewardVault: vaultPDA,
             responseAccount: responsePDA,
             recipient: responder.publicKey,
+            ...creatorProfileAccounts(wallet.publicKey, program),
             adminAccount: adminAccountPDA,
             signer: adminAuthority,
           })
@@ -438,14 +491,7 @@ describe("nodara - disburse_rewards", () => {
       await depositFunds(taskId, taskPDA, vaultPDA, minimalDeposit, program);
 
       const responder = Keypair.generate();
-      const [responsePDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("response"),
-          taskPDA.toBuffer(),
-          responder.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+      const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
       const sig = await provider.connection.requestAirdrop(
         responder.publicKey,
@@ -453,45 +499,16 @@ describe("nodara - disburse_rewards", () => {
       );
       await provider.connection.confirmTransaction(sig);
 
-      await program.methods
-        .submitResponse("QmPoor")
-        .accountsPartial({
-          taskAccount: taskPDA,
-          responseAccount: responsePDA,
-          responder: responder.publicKey,
-          admin: adminAuthority,
-          adminAccount: adminAccountPDA,
-        })
-        .rpc();
-
-      await program.methods
-        .verifyResponse()
-        .accountsPartial({
-          responseAccount: responsePDA,
-          adminAccount: adminAccountPDA,
-          signer: adminAuthority,
-        })
-        .rpc();
-
-      await program.methods
-        .markTaskComplete()
-        .accountsPartial({
-          taskAccount: taskPDA,
-          adminAccount: adminAccountPDA,
-          signer: adminAuthority,
-        })
-        .rpc();
-
+      // An underfunded task never opens, so there is nothing to disburse
       try {
         await program.methods
-          .disburseRewards()
+          .submitResponse(toCid("Poor"), [], null)
           .accountsPartial({
             taskAccount: taskPDA,
-            rewardVault: vaultPDA,
             responseAccount: responsePDA,
-            recipient: responder.publicKey,
+            responder: responder.publicKey,
+            admin: adminAuthority,
             adminAccount: adminAccountPDA,
-            signer: adminAuthority,
           })
           .rpc();
 
@@ -520,6 +537,7 @@ describe("nodara - disburse_rewards", () => {
             rewardVault: vaultPDA,
             responseAccount: responsePDAs[0],
             recipient: responders[0].publicKey,
+            ...creatorProfileAccounts(wallet.publicKey, program),
             adminAccount: adminAccountPDA,
             signer: intruder.publicKey,
           })
@@ -536,14 +554,7 @@ describe("nodara - disburse_rewards", () => {
       const { taskPDA, vaultPDA } = await createTaskWithVerifiedResponses(1);
 
       const fakeResponder = Keypair.generate();
-      const [fakeResponsePDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("response"),
-          taskPDA.toBuffer(),
-          fakeResponder.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+      const [fakeResponsePDA] = generateResponsePDA(taskPDA, fakeResponder.publicKey, program);
 
       try {
         await program.methods
@@ -553,6 +564,7 @@ describe("nodara - disburse_rewards", () => {
             rewardVault: vaultPDA,
             responseAccount: fakeResponsePDA,
             recipient: fakeResponder.publicKey,
+            ...creatorProfileAccounts(wallet.publicKey, program),
             adminAccount: adminAccountPDA,
             signer: adminAuthority,
           })
@@ -579,6 +591,7 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDA,
           recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
@@ -594,6 +607,7 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDA,
           recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
@@ -616,7 +630,7 @@ describe("nodara - disburse_rewards", () => {
       const rewardPerResponse = new anchor.BN(0);
       const maxResponses = 1;
       const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
-      const cid = "QmZeroReward";
+      const cid = toCid("ZeroReward");
 
       const { taskPDA, vaultPDA } = await createTask(
         wallet.publicKey,
@@ -626,14 +640,7 @@ describe("nodara - disburse_rewards", () => {
 
       // This should work but transfer 0 lamports
       const responder = Keypair.generate();
-      const [responsePDA] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("response"),
-          taskPDA.toBuffer(),
-          responder.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+      const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
       const sig = await provider.connection.requestAirdrop(
         responder.publicKey,
@@ -642,7 +649,7 @@ describe("nodara - disburse_rewards", () => {
       await provider.connection.confirmTransaction(sig);
 
       await program.methods
-        .submitResponse("QmZero")
+        .submitResponse(toCid("Zero"), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
           responseAccount: responsePDA,
@@ -653,8 +660,9 @@ describe("nodara - disburse_rewards", () => {
         .rpc();
 
       await program.methods
-        .verifyResponse()
+        .verifyResponse(null)
         .accountsPartial({
+          taskAccount: taskPDA,
           responseAccount: responsePDA,
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
@@ -681,6 +689,7 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDA,
           recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
@@ -716,6 +725,7 @@ describe("nodara - disburse_rewards", () => {
           rewardVault: vaultPDA,
           responseAccount: responsePDA,
           recipient: responder.publicKey,
+          ...creatorProfileAccounts(wallet.publicKey, program),
           adminAccount: adminAccountPDA,
           signer: adminAuthority,
         })
@@ -0,0 +1,155 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { creatorProfileAccounts, createTask, depositFunds, generateAdminPDA, generateResponsePDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - dispute", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  // Creates a single-response task whose only response is verified
+  const verifiedResponse = async () => {
+    const task = await createTask(wallet.publicKey, program, 1);
+    await depositFunds(task.taskId, task.taskPDA, task.vaultPDA, task.rewardPerResponse.muln(2), program);
+
+    const responder = Keypair.generate();
+    await submitResponse(task.taskPDA, responder, toCid("Disputed"), program, provider);
+
+    const [responsePDA] = generateResponsePDA(task.taskPDA, responder.publicKey, program);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: task.taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    const [disputePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), responsePDA.toBuffer()],
+      program.programId
+    );
+
+    return { ...task, responder, responsePDA, disputePDA };
+  };
+
+  const disburse = (taskPDA: PublicKey, vaultPDA: PublicKey, responsePDA: PublicKey, recipient: PublicKey) =>
+    program.methods
+      .disburseRewards()
+      .accountsPartial({
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        responseAccount: responsePDA,
+        recipient,
+        ...creatorProfileAccounts(wallet.publicKey, program),
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+  const resolve = (
+    taskPDA: PublicKey,
+    disputePDA: PublicKey,
+    responsePDA: PublicKey,
+    challenger: PublicKey,
+    responder: PublicKey,
+    inFavorOfChallenger: boolean
+  ) =>
+    program.methods
+      .resolveDispute(inFavorOfChallenger)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        disputeAccount: disputePDA,
+        responseAccount: responsePDA,
+        challenger,
+        responder,
+        adminAccount: adminAccountPDA,
+        arbitrator: adminAuthority,
+      })
+      .rpc();
+
+  it("Freezes payout until the dispute is resolved", async () => {
+    const { taskPDA, vaultPDA, responder, responsePDA, disputePDA } = await verifiedResponse();
+
+    await program.methods
+      .openDispute(new anchor.BN(0))
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, challenger: wallet.publicKey })
+      .rpc();
+
+    try {
+      await disburse(taskPDA, vaultPDA, responsePDA, responder.publicKey);
+      assert.fail("Expected PayoutFrozen");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PayoutFrozen");
+    }
+
+    await resolve(taskPDA, disputePDA, responsePDA, wallet.publicKey, responder.publicKey, false);
+
+    // Settled disputes are closed back to the challenger
+    const dispute = await program.account.disputeAccount.fetchNullable(disputePDA);
+    assert.isNull(dispute);
+
+    await disburse(taskPDA, vaultPDA, responsePDA, responder.publicKey);
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    assert.isTrue(response.isPaid);
+  });
+
+  it("Fails to pay out before the dispute window closes", async () => {
+    const { taskPDA, vaultPDA, responder, responsePDA } = await verifiedResponse();
+
+    try {
+      await disburse(taskPDA, vaultPDA, responsePDA, responder.publicKey);
+      assert.fail("Expected DisputeWindowOpen");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DisputeWindowOpen");
+    }
+  });
+
+  it("Refunds the bond and rejects the response when the challenger wins", async () => {
+    const { taskPDA, responder, responsePDA, disputePDA } = await verifiedResponse();
+    const challenger = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(challenger.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    const bond = new anchor.BN(0.02 * LAMPORTS_PER_SOL);
+    await program.methods
+      .openDispute(bond)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, challenger: challenger.publicKey })
+      .signers([challenger])
+      .rpc();
+
+    // The bond comes back together with the rent of the closed dispute account
+    const held = await provider.connection.getBalance(disputePDA);
+    const before = await provider.connection.getBalance(challenger.publicKey);
+    await resolve(taskPDA, disputePDA, responsePDA, challenger.publicKey, responder.publicKey, true);
+    const after = await provider.connection.getBalance(challenger.publicKey);
+
+    assert.equal(after - before, held);
+    assert.isNull(await provider.connection.getAccountInfo(disputePDA));
+
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    assert.isTrue(response.isRejected);
+    assert.isFalse(response.isVerified);
+  });
+
+  it("Fails if a third party posts less than the minimum bond", async () => {
+    const { taskPDA, responsePDA } = await verifiedResponse();
+    const challenger = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(challenger.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .openDispute(new anchor.BN(1_000))
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, challenger: challenger.publicKey })
+        .signers([challenger])
+        .rpc();
+      assert.fail("Expected InsufficientBond");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientBond");
+    }
+  });
+});
@@ -0,0 +1,91 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  createTask,
+  fundTask,
+  generateAdminPDA,
+  generateResponsePDA,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - escrow_locks", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const fundedTask = async () => {
+    const task = await createTask(wallet.publicKey, program, 3);
+    await fundTask(task.taskId, task.taskPDA, task.vaultPDA, program);
+    return task;
+  };
+
+  it("Locks the reward for each submitted response", async () => {
+    const { taskPDA, vaultPDA, rewardPerResponse } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("Locked"), program, provider);
+
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+
+    assert.equal(response.owed.toString(), rewardPerResponse.toString());
+    assert.equal(vault.lockedBalance.toString(), rewardPerResponse.toString());
+  });
+
+  it("Releases the lock when the response is rejected", async () => {
+    const { taskPDA, vaultPDA } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("Released"), program, provider);
+
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    await program.methods
+      .rejectResponse({ invalid: {} })
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(response.owed.toNumber(), 0);
+    assert.equal(vault.lockedBalance.toNumber(), 0);
+  });
+
+  it("Keeps locked rewards in the vault on refund", async () => {
+    const { taskPDA, vaultPDA, rewardPerResponse } = await fundedTask();
+    await submitResponse(taskPDA, Keypair.generate(), toCid("Pending"), program, provider);
+
+    const before = await program.account.rewardVault.fetch(vaultPDA);
+    const creatorBefore = await provider.connection.getBalance(wallet.publicKey);
+
+    await program.methods
+      .refundRemaining()
+      .accountsPartial({ creator: wallet.publicKey, taskAccount: taskPDA, rewardVault: vaultPDA })
+      .rpc();
+
+    const after = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(after.balance.toString(), rewardPerResponse.toString());
+    assert.equal(after.lockedBalance.toString(), rewardPerResponse.toString());
+
+    // The creator gets back everything else, less the transaction fee
+    const creatorAfter = await provider.connection.getBalance(wallet.publicKey);
+    const refunded = before.balance.sub(rewardPerResponse).toNumber();
+    assert.approximately(creatorAfter - creatorBefore, refunded, 10_000);
+
+    // The task stays open for settling the locked response but takes no new ones
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.isTrue(task.isCancelled);
+    try {
+      await submitResponse(taskPDA, Keypair.generate(), toCid("AfterRefund"), program, provider);
+      assert.fail("Expected TaskCancelled");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TaskCancelled");
+    }
+  });
+});
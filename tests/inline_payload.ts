@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, generateAdminPDA, generateResponsePDA, submitResponse, toCid } from "./test-utils";
+
+describe("nodara - inline_payload", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const fundedTask = async () => {
+    const task = await createTask(wallet.publicKey, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(task.maxResponses * 2)),
+      program
+    );
+    return task;
+  };
+
+  const attachPayload = (taskPDA: PublicKey, responder: PublicKey, payload: Buffer) => {
+    const [responsePDA] = generateResponsePDA(taskPDA, responder, program);
+    return program.methods
+      .attachResponsePayload(payload)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: responsePDA,
+        admin: adminAuthority,
+        adminAccount: adminAccountPDA,
+      })
+      .rpc()
+      .then(() => responsePDA);
+  };
+
+  it("Stores an inline task input", async () => {
+    const { taskId, taskPDA } = await fundedTask();
+    const input = Buffer.from(JSON.stringify({ lat: 52.37, lon: 4.89 }));
+
+    await program.methods
+      .setTaskInput(taskId, input)
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(Buffer.from(task.input).toString(), input.toString());
+  });
+
+  it("Fails with a task input above the limit", async () => {
+    const { taskId } = await fundedTask();
+
+    try {
+      await program.methods
+        .setTaskInput(taskId, Buffer.alloc(513))
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      assert.fail("Expected InputTooLarge");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InputTooLarge");
+    }
+  });
+
+  it("Attaches an inline payload to a response", async () => {
+    const { taskPDA } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("Inline"), program, provider);
+
+    const payload = Buffer.from("temperature=21.5");
+    const responsePDA = await attachPayload(taskPDA, responder.publicKey, payload);
+
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    assert.equal(Buffer.from(response.payload).toString(), payload.toString());
+  });
+
+  it("Fails with a response payload above the limit", async () => {
+    const { taskPDA } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("InlineLarge"), program, provider);
+
+    try {
+      await attachPayload(taskPDA, responder.publicKey, Buffer.alloc(513));
+      assert.fail("Expected InputTooLarge");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InputTooLarge");
+    }
+  });
+});
@@ -0,0 +1,177 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  creatorProfileAccounts,
+  createTask,
+  depositFunds,
+  generateAdminPDA,
+  generateProtocolStatsPDA,
+  generateResponsePDA,
+  generateVaultPDA,
+  setDisputeWindow,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - protocol_stats", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const [statsPDA] = generateProtocolStatsPDA(program);
+
+  // Other suites update the same account, so assert on deltas
+  const fetchStats = () => program.account.protocolStats.fetch(statsPDA);
+
+  it("Counts created and completed tasks", async () => {
+    const before = await fetchStats();
+
+    const { taskPDA } = await createTask(wallet.publicKey, program);
+    const created = await fetchStats();
+    assert.equal(created.totalTasks.sub(before.totalTasks).toNumber(), 1);
+    assert.equal(created.openTasks.sub(before.openTasks).toNumber(), 1);
+
+    await program.methods
+      .markTaskComplete()
+      .accountsPartial({ taskAccount: taskPDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    const completed = await fetchStats();
+    assert.equal(completed.totalTasks.toString(), created.totalTasks.toString());
+    assert.equal(created.openTasks.sub(completed.openTasks).toNumber(), 1);
+  });
+
+  it("Counts escrow, fees, responses, verifications and disbursements", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await createTask(wallet.publicKey, program, 1);
+    await setDisputeWindow(taskId, 0, program);
+    const before = await fetchStats();
+
+    const deposit = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    await depositFunds(taskId, taskPDA, vaultPDA, deposit, program);
+
+    const fee = deposit.muln(69).divn(1000);
+    const funded = await fetchStats();
+    assert.equal(funded.totalEscrowed.sub(before.totalEscrowed).toString(), deposit.sub(fee).toString());
+    assert.equal(funded.totalFees.sub(before.totalFees).toString(), fee.toString());
+
+    // The only response fills the task, which closes it
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("StatsResponse"), program, provider);
+    const submitted = await fetchStats();
+    assert.equal(submitted.totalResponses.sub(funded.totalResponses).toNumber(), 1);
+    assert.equal(funded.openTasks.sub(submitted.openTasks).toNumber(), 1);
+
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    const verify = () =>
+      program.methods
+        .verifyResponse(null)
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+        .rpc();
+
+    // Verifying twice is a no-op and only counts once
+    await verify();
+    await verify();
+    const verified = await fetchStats();
+    assert.equal(verified.verifiedResponses.sub(submitted.verifiedResponses).toNumber(), 1);
+
+    await program.methods
+      .disburseRewards()
+      .accountsPartial({
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        responseAccount: responsePDA,
+        recipient: responder.publicKey,
+        ...creatorProfileAccounts(wallet.publicKey, program),
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+    const disbursed = await fetchStats();
+    assert.equal(disbursed.totalDisbursed.sub(verified.totalDisbursed).toString(), rewardPerResponse.toString());
+  });
+
+  it("Closes refunded tasks and counts the refund", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    const deposit = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    await depositFunds(taskId, taskPDA, vaultPDA, deposit, program);
+    const before = await fetchStats();
+
+    await program.methods
+      .refundRemaining()
+      .accountsPartial({ creator: wallet.publicKey, taskAccount: taskPDA, rewardVault: vaultPDA })
+      .rpc();
+
+    const refunded = await fetchStats();
+    const net = deposit.sub(deposit.muln(69).divn(1000));
+    assert.equal(before.openTasks.sub(refunded.openTasks).toNumber(), 1);
+    assert.equal(refunded.totalRefunded.sub(before.totalRefunded).toString(), net.toString());
+
+    // Completing the cancelled task does not close it a second time
+    await program.methods
+      .markTaskComplete()
+      .accountsPartial({ taskAccount: taskPDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+    const completed = await fetchStats();
+    assert.equal(completed.openTasks.toString(), refunded.openTasks.toString());
+  });
+
+  it("Counts the unspent escrow of a closed template as refunded", async () => {
+    const templateId = new anchor.BN(Math.floor(Math.random() * 1_000_000) + 1);
+    const [templatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("template"), wallet.publicKey.toBuffer(), Buffer.from(templateId.toArray("le", 8))],
+      program.programId
+    );
+    const [templateVaultPDA] = generateVaultPDA(templatePDA, program);
+
+    await program.methods
+      .createTaskTemplate(templateId, {
+        rewardPerResponse: new anchor.BN(100_000),
+        maxResponses: 5,
+        firstStart: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        interval: new anchor.BN(3600),
+        epochDuration: new anchor.BN(600),
+        totalEpochs: 2,
+        cid: toCid("StatsTemplate"),
+      })
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+    await program.methods
+      .fundTaskTemplate(templateId, new anchor.BN(0.01 * LAMPORTS_PER_SOL))
+      .accountsPartial({
+        template: templatePDA,
+        templateVault: templateVaultPDA,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+        creator: wallet.publicKey,
+      })
+      .rpc();
+
+    const { balance: unspent } = await program.account.rewardVault.fetch(templateVaultPDA);
+    assert.isTrue(unspent.gtn(0));
+    const before = await fetchStats();
+
+    await program.methods
+      .closeTaskTemplate()
+      .accountsPartial({ template: templatePDA, templateVault: templateVaultPDA, creator: wallet.publicKey })
+      .rpc();
+
+    const closed = await fetchStats();
+    assert.equal(closed.totalRefunded.sub(before.totalRefunded).toString(), unspent.toString());
+  });
+
+  it("Creates the stats account only once", async () => {
+    try {
+      await program.methods.initProtocolStats().accounts({ admin: adminAuthority }).rpc();
+      assert.fail("Expected the stats account to exist already");
+    } catch (err: any) {
+      expect(err.message).to.match(/already in use/);
+    }
+  });
+});
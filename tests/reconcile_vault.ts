@@ -0,0 +1,62 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram, Transaction } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, fundTask, generateAdminPDA } from "./test-utils";
+
+describe("nodara - reconcile_vault", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const reconcile = (taskPDA: anchor.web3.PublicKey, admin = adminAuthority) =>
+    program.methods
+      .reconcileVault()
+      .accountsPartial({ escrowOwner: taskPDA, adminAccount: adminAccountPDA, admin });
+
+  it("Sweeps lamports sent directly to the vault", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
+
+    const donation = 0.01 * LAMPORTS_PER_SOL;
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: wallet.publicKey, toPubkey: vaultPDA, lamports: donation })
+      )
+    );
+
+    const before = await provider.connection.getBalance(vaultPDA);
+    const tracked = (await program.account.rewardVault.fetch(vaultPDA)).balance;
+
+    await reconcile(taskPDA).rpc();
+
+    const after = await provider.connection.getBalance(vaultPDA);
+    assert.equal(before - after, donation);
+    assert.equal((await program.account.rewardVault.fetch(vaultPDA)).balance.toString(), tracked.toString());
+
+    // Nothing is left to sweep the second time
+    await reconcile(taskPDA).rpc();
+    assert.equal(await provider.connection.getBalance(vaultPDA), after);
+  });
+
+  it("Fails if a non-admin reconciles", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
+
+    const intruder = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(intruder.publicKey, 0.1 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await reconcile(taskPDA, intruder.publicKey).signers([intruder]).rpc();
+      expect.fail("Expected Unauthorized but it succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+});
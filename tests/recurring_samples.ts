@@ -0,0 +1,84 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  createTask,
+  depositFunds,
+  generateResponderTaskPDA,
+  generateResponsePDA,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - recurring_samples", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+
+  const samplingTask = async (perResponder: number) => {
+    const task = await createTask(wallet.publicKey, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(task.maxResponses * 2)),
+      program
+    );
+    await program.methods
+      .setMaxResponsesPerResponder(task.taskId, perResponder)
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+    return task;
+  };
+
+  it("Accepts several indexed samples from one responder", async () => {
+    const { taskPDA } = await samplingTask(3);
+    const device = Keypair.generate();
+
+    for (let i = 0; i < 3; i++) {
+      await submitResponse(taskPDA, device, toCid(`Sample${i}`), program, provider, [], null, i);
+    }
+
+    const [statePDA] = generateResponderTaskPDA(taskPDA, device.publicKey, program);
+    const state = await program.account.responderTaskState.fetch(statePDA);
+    assert.equal(state.submitted, 3);
+
+    const last = await program.account.responseAccount.fetch(generateResponsePDA(taskPDA, device.publicKey, program, 2)[0]);
+    assert.equal(last.index, 2);
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.responsesReceived, 3);
+  });
+
+  it("Fails once a responder reaches the limit", async () => {
+    const { taskPDA } = await samplingTask(2);
+    const device = Keypair.generate();
+
+    await submitResponse(taskPDA, device, toCid("First"), program, provider, [], null, 0);
+    await submitResponse(taskPDA, device, toCid("Second"), program, provider, [], null, 1);
+
+    try {
+      await submitResponse(taskPDA, device, toCid("Third"), program, provider, [], null, 2);
+      assert.fail("Expected ResponderLimitReached");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponderLimitReached");
+    }
+  });
+
+  it("Fails with a zero per-responder limit", async () => {
+    const { taskId } = await createTask(wallet.publicKey, program);
+
+    try {
+      await program.methods
+        .setMaxResponsesPerResponder(taskId, 0)
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      assert.fail("Expected InvalidMaxResponses");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidMaxResponses");
+    }
+  });
+});
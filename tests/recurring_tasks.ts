@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { generateAdminPDA, generateCreatorProfilePDA, generateTaskPDA, generateVaultPDA, toCid } from "./test-utils";
+
+describe("nodara - recurring_tasks", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const [profilePDA] = generateCreatorProfilePDA(wallet.publicKey, program);
+
+  const rewardPerResponse = new anchor.BN(100_000);
+  const maxResponses = 5;
+  const epochEscrow = rewardPerResponse.muln(maxResponses);
+
+  // Grossed up so the net deposit after the platform fee covers the escrow
+  const grossUp = (net: anchor.BN) => net.muln(1000).divn(931).addn(1);
+
+  const createTemplate = async (deposit: anchor.BN, startOffset = -10) => {
+    const templateId = new anchor.BN(Math.floor(Math.random() * 1_000_000) + 1);
+    const [templatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("template"), wallet.publicKey.toBuffer(), Buffer.from(templateId.toArray("le", 8))],
+      program.programId
+    );
+    const [templateVaultPDA] = generateVaultPDA(templatePDA, program);
+
+    await program.methods
+      .createTaskTemplate(templateId, {
+        rewardPerResponse,
+        maxResponses,
+        firstStart: new anchor.BN(Math.floor(Date.now() / 1000) + startOffset),
+        interval: new anchor.BN(3600),
+        epochDuration: new anchor.BN(600),
+        totalEpochs: 3,
+        cid: toCid("HourlyTemperature"),
+      })
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    await program.methods
+      .fundTaskTemplate(templateId, deposit)
+      .accountsPartial({
+        template: templatePDA,
+        templateVault: templateVaultPDA,
+        adminAccount: adminAccountPDA,
+        adminAuthority: wallet.publicKey,
+        creator: wallet.publicKey,
+      })
+      .rpc();
+
+    return { templateId, templatePDA, templateVaultPDA };
+  };
+
+  const openEpoch = (templatePDA: PublicKey, templateVaultPDA: PublicKey, epoch: number) => {
+    const [epochTaskPDA] = generateTaskPDA(templatePDA, new anchor.BN(epoch), program);
+    const [epochVaultPDA] = generateVaultPDA(epochTaskPDA, program);
+    return program.methods
+      .openNextEpoch()
+      .accountsPartial({
+        template: templatePDA,
+        templateVault: templateVaultPDA,
+        epochTask: epochTaskPDA,
+        epochVault: epochVaultPDA,
+        payer: wallet.publicKey,
+      })
+      .rpc()
+      .then(() => ({ epochTaskPDA, epochVaultPDA }));
+  };
+
+  it("Opens the first epoch from the template vault", async () => {
+    const { templatePDA, templateVaultPDA } = await createTemplate(grossUp(epochEscrow.muln(2)));
+
+    const { epochTaskPDA, epochVaultPDA } = await openEpoch(templatePDA, templateVaultPDA, 1);
+
+    const task = await program.account.taskAccount.fetch(epochTaskPDA);
+    assert.equal(task.taskId.toNumber(), 1);
+    assert.equal(task.creator.toBase58(), templatePDA.toBase58());
+    assert.equal(task.maxResponses, maxResponses);
+
+    // The window runs from the scheduled start, not from when the crank ran
+    const template = await program.account.taskTemplate.fetch(templatePDA);
+    assert.equal(task.deadline.toNumber(), template.firstStart.toNumber() + 600);
+
+    const epochVault = await program.account.rewardVault.fetch(epochVaultPDA);
+    assert.equal(epochVault.balance.toString(), epochEscrow.toString());
+
+    assert.equal(template.epochsOpened, 1);
+  });
+
+  it("Skips an epoch whose window passed before it was opened", async () => {
+    const { templatePDA, templateVaultPDA } = await createTemplate(grossUp(epochEscrow.muln(2)), -900);
+
+    try {
+      await openEpoch(templatePDA, templateVaultPDA, 1);
+      assert.fail("Expected EpochWindowPassed");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EpochWindowPassed");
+    }
+
+    await program.methods.skipEpoch().accountsPartial({ template: templatePDA }).rpc();
+
+    const template = await program.account.taskTemplate.fetch(templatePDA);
+    assert.equal(template.epochsOpened, 1);
+    assert.equal(template.liveEpochs, 0);
+  });
+
+  it("Fails to open an epoch before its start time", async () => {
+    const { templatePDA, templateVaultPDA } = await createTemplate(grossUp(epochEscrow.muln(2)));
+    await openEpoch(templatePDA, templateVaultPDA, 1);
+
+    try {
+      await openEpoch(templatePDA, templateVaultPDA, 2);
+      assert.fail("Expected EpochNotStarted");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EpochNotStarted");
+    }
+  });
+
+  it("Fails when the template vault cannot cover an epoch", async () => {
+    const { templatePDA, templateVaultPDA } = await createTemplate(grossUp(epochEscrow.divn(2)));
+
+    try {
+      await openEpoch(templatePDA, templateVaultPDA, 1);
+      assert.fail("Expected InsufficientTemplateFunds");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InsufficientTemplateFunds");
+    }
+  });
+
+  it("Refunds an open epoch to the template creator before closing the template", async () => {
+    const { templatePDA, templateVaultPDA } = await createTemplate(grossUp(epochEscrow.muln(2)));
+    const { epochTaskPDA, epochVaultPDA } = await openEpoch(templatePDA, templateVaultPDA, 1);
+
+    const closeTemplate = () =>
+      program.methods
+        .closeTaskTemplate()
+        .accountsPartial({ template: templatePDA, templateVault: templateVaultPDA, creator: wallet.publicKey })
+        .rpc();
+
+    try {
+      await closeTemplate();
+      assert.fail("Expected EpochsStillOpen");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("EpochsStillOpen");
+    }
+
+    const balanceBefore = await provider.connection.getBalance(wallet.publicKey);
+    await program.methods
+      .refundEpoch()
+      .accountsPartial({
+        template: templatePDA,
+        epochTask: epochTaskPDA,
+        epochVault: epochVaultPDA,
+        creator: wallet.publicKey,
+      })
+      .rpc();
+
+    // Escrow plus vault rent, less the transaction fee
+    const balanceAfter = await provider.connection.getBalance(wallet.publicKey);
+    assert.isAbove(balanceAfter - balanceBefore, epochEscrow.toNumber());
+    assert.isNull(await provider.connection.getAccountInfo(epochVaultPDA));
+
+    const task = await program.account.taskAccount.fetch(epochTaskPDA);
+    assert.isTrue(task.isCancelled);
+
+    // The unspent template escrow counts as refunded to the creator
+    const { balance: unspent } = await program.account.rewardVault.fetch(templateVaultPDA);
+    const profileBefore = await program.account.creatorProfile.fetch(profilePDA);
+
+    await closeTemplate();
+    assert.isNull(await provider.connection.getAccountInfo(templatePDA));
+
+    const profileAfter = await program.account.creatorProfile.fetch(profilePDA);
+    assert.equal(profileAfter.lamportsRefunded.sub(profileBefore.lamportsRefunded).toString(), unspent.toString());
+  });
+});
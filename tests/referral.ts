@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTaskAccounts, generateAdminPDA, generateCreatorProfilePDA, generateTaskPDA, generateVaultPDA, toCid } from "./test-utils";
+
+describe("nodara - referral", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const shareBps = 2_000;
+
+  const referrer = Keypair.generate();
+  const creator = Keypair.generate();
+  const [accrualPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("referrer"), referrer.publicKey.toBuffer()],
+    program.programId
+  );
+
+  const fund = async (keypair: Keypair, sol: number) => {
+    const sig = await provider.connection.requestAirdrop(keypair.publicKey, sol * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+  };
+
+  const createReferredTask = async () => {
+    const taskId = new anchor.BN(Math.floor(Math.random() * 1_000_000));
+    const [taskPDA] = generateTaskPDA(creator.publicKey, taskId, program);
+    const [vaultPDA] = generateVaultPDA(taskPDA, program);
+
+    await program.methods
+      .createTask(taskId, new anchor.BN(100_000), 2, new anchor.BN(Math.floor(Date.now() / 1000) + 3600), toCid("Referred"))
+      .accountsPartial(await createTaskAccounts(creator.publicKey, program))
+      .signers([creator])
+      .rpc();
+
+    return { taskId, taskPDA, vaultPDA };
+  };
+
+  const deposit = (taskId: anchor.BN, taskPDA: PublicKey, vaultPDA: PublicKey, amount: anchor.BN, referrerAccrual: PublicKey | null) =>
+    program.methods
+      .depositFunds(taskId, amount)
+      .accountsPartial({
+        creator: creator.publicKey,
+        taskAccount: taskPDA,
+        rewardVault: vaultPDA,
+        referrerAccrual,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+      })
+      .signers([creator])
+      .rpc();
+
+  before(async () => {
+    await fund(referrer, 1);
+    await fund(creator, 2);
+
+    await program.methods
+      .registerReferrer()
+      .accountsPartial({ referrer: referrer.publicKey })
+      .signers([referrer])
+      .rpc();
+
+    await program.methods
+      .setReferrer()
+      .accountsPartial({ referrerAccrual: accrualPDA, creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    await program.methods
+      .setReferralShare(shareBps)
+      .accountsPartial({ adminAccount: adminAccountPDA, admin: adminAuthority })
+      .rpc();
+  });
+
+  it("Records the referrer on the creator profile", async () => {
+    const [profilePDA] = generateCreatorProfilePDA(creator.publicKey, program);
+    const profile = await program.account.creatorProfile.fetch(profilePDA);
+    assert.equal(profile.referrer.toBase58(), referrer.publicKey.toBase58());
+  });
+
+  it("Routes a share of the platform fee to the referrer", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createReferredTask();
+    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    const fee = amount.muln(69).divn(1000);
+    const share = fee.muln(shareBps).divn(10_000);
+
+    const before = await program.account.referrerAccrual.fetch(accrualPDA);
+    await deposit(taskId, taskPDA, vaultPDA, amount, accrualPDA);
+
+    const after = await program.account.referrerAccrual.fetch(accrualPDA);
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(after.unclaimed.sub(before.unclaimed).toString(), share.toString());
+    assert.equal(vault.balance.toString(), amount.sub(fee).toString());
+  });
+
+  it("Routes a share of template funding fees to the referrer", async () => {
+    const templateId = new anchor.BN(Math.floor(Math.random() * 1_000_000) + 1);
+    const [templatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("template"), creator.publicKey.toBuffer(), Buffer.from(templateId.toArray("le", 8))],
+      program.programId
+    );
+    await program.methods
+      .createTaskTemplate(templateId, {
+        rewardPerResponse: new anchor.BN(100_000),
+        maxResponses: 2,
+        firstStart: new anchor.BN(Math.floor(Date.now() / 1000)),
+        interval: new anchor.BN(3600),
+        epochDuration: new anchor.BN(600),
+        totalEpochs: 1,
+        cid: toCid("ReferredTemplate"),
+      })
+      .accounts({ creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+
+    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    const share = amount.muln(69).divn(1000).muln(shareBps).divn(10_000);
+
+    const before = await program.account.referrerAccrual.fetch(accrualPDA);
+    await program.methods
+      .fundTaskTemplate(templateId, amount)
+      .accountsPartial({
+        template: templatePDA,
+        templateVault: generateVaultPDA(templatePDA, program)[0],
+        referrerAccrual: accrualPDA,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const after = await program.account.referrerAccrual.fetch(accrualPDA);
+    assert.equal(after.unclaimed.sub(before.unclaimed).toString(), share.toString());
+  });
+
+  it("Fails to deposit without the referrer's accrual account", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createReferredTask();
+
+    try {
+      await deposit(taskId, taskPDA, vaultPDA, new anchor.BN(0.1 * LAMPORTS_PER_SOL), null);
+      assert.fail("Expected ReferrerAccountMismatch");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ReferrerAccountMismatch");
+    }
+  });
+
+  it("Lets the referrer claim accrued fees", async () => {
+    const { unclaimed } = await program.account.referrerAccrual.fetch(accrualPDA);
+    const before = await provider.connection.getBalance(referrer.publicKey);
+
+    await program.methods
+      .claimReferralFees()
+      .accountsPartial({ referrer: referrer.publicKey })
+      .signers([referrer])
+      .rpc();
+
+    const after = await provider.connection.getBalance(referrer.publicKey);
+    const accrual = await program.account.referrerAccrual.fetch(accrualPDA);
+    assert.equal(after - before, unclaimed.toNumber());
+    assert.equal(accrual.unclaimed.toNumber(), 0);
+  });
+
+  it("Fails to change an existing referrer", async () => {
+    try {
+      await program.methods
+        .setReferrer()
+        .accountsPartial({ referrerAccrual: accrualPDA, creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+      assert.fail("Expected ReferrerAlreadySet");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ReferrerAlreadySet");
+    }
+  });
+
+  it("Fails to set a share above the maximum", async () => {
+    try {
+      await program.methods
+        .setReferralShare(5_001)
+        .accountsPartial({ adminAccount: adminAccountPDA, admin: adminAuthority })
+        .rpc();
+      assert.fail("Expected InvalidReferralShare");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidReferralShare");
+    }
+  });
+});
@@ -3,7 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
-import { createTask, generateAdminPDA } from "./test-utils";
+import { createTask, generateAdminPDA, generateResponsePDA, toCid } from "./test-utils";
 
 describe("nodara - refund_funds", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -97,12 +97,9 @@ describe("nodara - refund_funds", () => {
 
     // Submit Response
     const responder = Keypair.generate();
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
     await program.methods
-      .submitResponse("QmTestCID")
+      .submitResponse(toCid("TestCID"), [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA,
@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, generateAdminPDA } from "./test-utils";
+
+describe("nodara - sponsor_task", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const netOf = (amount: anchor.BN) => amount.sub(amount.muln(69).divn(1000));
+
+  const generateSponsorPDA = (taskPDA: PublicKey, sponsor: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("sponsor"), taskPDA.toBuffer(), sponsor.toBuffer()],
+      program.programId
+    );
+
+  const fundedSponsor = async () => {
+    const sponsor = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(sponsor.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+    return sponsor;
+  };
+
+  const sponsorTask = (taskPDA: PublicKey, sponsor: Keypair, amount: anchor.BN) =>
+    program.methods
+      .sponsorTask(amount)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        sponsor: sponsor.publicKey,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+      })
+      .signers([sponsor])
+      .rpc();
+
+  const claimRefund = (taskPDA: PublicKey, sponsor: Keypair) =>
+    program.methods
+      .claimSponsorRefund()
+      .accountsPartial({
+        sponsorAccount: generateSponsorPDA(taskPDA, sponsor.publicKey)[0],
+        sponsor: sponsor.publicKey,
+      })
+      .signers([sponsor])
+      .rpc();
+
+  const refund = (taskPDA: PublicKey, vaultPDA: PublicKey) =>
+    program.methods
+      .refundRemaining()
+      .accountsPartial({ creator: wallet.publicKey, taskAccount: taskPDA, rewardVault: vaultPDA })
+      .rpc();
+
+  it("Records sponsor contributions in the task vault", async () => {
+    const { taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    const sponsor = await fundedSponsor();
+    const amount = new anchor.BN(0.02 * LAMPORTS_PER_SOL);
+
+    await sponsorTask(taskPDA, sponsor, amount);
+    await sponsorTask(taskPDA, sponsor, amount);
+
+    const [sponsorPDA] = generateSponsorPDA(taskPDA, sponsor.publicKey);
+    const record = await program.account.sponsorAccount.fetch(sponsorPDA);
+    assert.equal(record.contributed.toString(), netOf(amount).muln(2).toString());
+
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(vault.sponsored.toString(), record.contributed.toString());
+    assert.equal(vault.sponsorCount, 1);
+  });
+
+  it("Refunds sponsors pro-rata on cancellation", async () => {
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    const sponsorA = await fundedSponsor();
+    const sponsorB = await fundedSponsor();
+
+    await depositFunds(taskId, taskPDA, vaultPDA, new anchor.BN(0.04 * LAMPORTS_PER_SOL), program);
+    await sponsorTask(taskPDA, sponsorA, new anchor.BN(0.02 * LAMPORTS_PER_SOL));
+    await sponsorTask(taskPDA, sponsorB, new anchor.BN(0.01 * LAMPORTS_PER_SOL));
+
+    const funded = await program.account.rewardVault.fetch(vaultPDA);
+    const [recordA] = generateSponsorPDA(taskPDA, sponsorA.publicKey);
+    const contributedA = (await program.account.sponsorAccount.fetch(recordA)).contributed;
+    const creatorBefore = await provider.connection.getBalance(wallet.publicKey);
+
+    // The creator's refund needs no sponsor accounts
+    await refund(taskPDA, vaultPDA);
+
+    const sponsorShare = funded.balance.mul(funded.sponsored).div(funded.deposited);
+    const creatorShare = funded.balance.sub(sponsorShare);
+    const creatorAfter = await provider.connection.getBalance(wallet.publicKey);
+    // Less the transaction fee
+    assert.isAbove(creatorAfter - creatorBefore, creatorShare.toNumber() - 10_000);
+
+    const refunded = await program.account.rewardVault.fetch(vaultPDA);
+    assert.isTrue(refunded.isSettled);
+    assert.equal(refunded.balance.toNumber(), 0);
+    assert.equal(refunded.sponsorPool.toString(), sponsorShare.toString());
+
+    await claimRefund(taskPDA, sponsorA);
+
+    const claimedA = refunded.sponsorReleased.mul(contributedA).div(funded.sponsored);
+    const afterClaim = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(refunded.sponsorPool.sub(afterClaim.sponsorPool).toString(), claimedA.toString());
+    assert.isNull(await provider.connection.getAccountInfo(recordA));
+  });
+
+  it("Keeps the vault open until every sponsor has claimed", async () => {
+    const { taskPDA, vaultPDA } = await createTask(wallet.publicKey, program);
+    const sponsorA = await fundedSponsor();
+    const sponsorB = await fundedSponsor();
+    await sponsorTask(taskPDA, sponsorA, new anchor.BN(0.02 * LAMPORTS_PER_SOL));
+    await sponsorTask(taskPDA, sponsorB, new anchor.BN(0.02 * LAMPORTS_PER_SOL));
+
+    // Nothing has been released yet
+    try {
+      await claimRefund(taskPDA, sponsorA);
+      expect.fail("Expected NoSponsorRefund but it succeeded.");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NoSponsorRefund");
+    }
+
+    await refund(taskPDA, vaultPDA);
+    await claimRefund(taskPDA, sponsorA);
+    await claimRefund(taskPDA, sponsorB);
+
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(vault.sponsorCount, 0);
+
+    // Refunding again closes the settled vault
+    await refund(taskPDA, vaultPDA);
+    assert.isNull(await provider.connection.getAccountInfo(vaultPDA));
+  });
+});
@@ -3,7 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
-import { generateAdminPDA, generateTaskPDA, generateVaultPDA } from "./test-utils";
+import { cidDigest, createTaskAccounts, generateAdminPDA, generateResponsePDA, generateTaskPDA, generateVaultPDA, toCid } from "./test-utils";
 
 describe("nodara - submit_response", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
@@ -18,14 +18,14 @@ describe("nodara - submit_response", () => {
     const taskId = new anchor.BN(Math.floor(Math.random() * 1_000_000));
     const rewardPerResponse = new anchor.BN(100_000);
     const deadline = deadlineParam ?? new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
-    const cid = "QmCID" + taskId.toString();
+    const cid = toCid("CID" + taskId.toString());
 
     const [taskPDA] = generateTaskPDA(wallet.publicKey, taskId, program);
     const [vaultPDA] = generateVaultPDA(taskPDA, program);
 
     await program.methods
       .createTask(taskId, rewardPerResponse, maxResponses, deadline, cid)
-      .accounts({ creator: wallet.publicKey })
+      .accountsPartial(await createTaskAccounts(wallet.publicKey, program))
       .rpc();
 
     return { taskId, taskPDA, vaultPDA };
@@ -49,13 +49,10 @@ describe("nodara - submit_response", () => {
   it("Successfully submits response", async () => {
     const { taskPDA } = await depositFunds();
     const responder = Keypair.generate();
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
     await program.methods
-      .submitResponse("QmTestCID")
+      .submitResponse(toCid("TestCID"), [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA,
@@ -66,7 +63,7 @@ describe("nodara - submit_response", () => {
       .rpc();
 
     const responseAcc = await program.account.responseAccount.fetch(responsePDA);
-    expect(responseAcc.cid).to.equal("QmTestCID");
+    expect(responseAcc.cid.digest).to.deep.equal(cidDigest("TestCID"));
     expect(responseAcc.responder.toBase58()).to.equal(responder.publicKey.toBase58());
   });
 
@@ -76,16 +73,13 @@ describe("nodara - submit_response", () => {
 
     const { taskPDA } = await depositFunds(deadline);
     const responder = Keypair.generate();
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
     await new Promise((r) => setTimeout(r, 4000));
 
     try {
       await program.methods
-        .submitResponse("QmLate")
+        .submitResponse(toCid("Late"), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
           responseAccount: responsePDA,
@@ -106,13 +100,10 @@ describe("nodara - submit_response", () => {
     const { taskPDA } = await depositFunds(undefined, maxResponses);
 
     const responder1 = Keypair.generate();
-    const [responsePDA1] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder1.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA1] = generateResponsePDA(taskPDA, responder1.publicKey, program);
 
     await program.methods
-      .submitResponse("QmCID1")
+      .submitResponse(toCid("CID1"), [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA1,
@@ -123,14 +114,11 @@ describe("nodara - submit_response", () => {
       .rpc();
 
     const responder2 = Keypair.generate();
-    const [responsePDA2] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder2.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA2] = generateResponsePDA(taskPDA, responder2.publicKey, program);
 
     try {
       await program.methods
-        .submitResponse("QmCID2")
+        .submitResponse(toCid("CID2"), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
           responseAccount: responsePDA2,
@@ -145,17 +133,14 @@ describe("nodara - submit_response", () => {
     }
   });
 
-  it("Fails if same responder tries twice", async () => {
+  it("Fails if same responder exceeds the per-responder limit", async () => {
     const { taskPDA } = await depositFunds();
 
     const responder = Keypair.generate();
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
     await program.methods
-      .submitResponse("QmOnce")
+      .submitResponse(toCid("Once"), [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA,
@@ -165,12 +150,13 @@ describe("nodara - submit_response", () => {
       })
       .rpc();
 
+    const [secondResponsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program, 1);
     try {
       await program.methods
-        .submitResponse("QmTwice")
+        .submitResponse(toCid("Twice"), [], null)
         .accountsPartial({
           taskAccount: taskPDA,
-          responseAccount: responsePDA,
+          responseAccount: secondResponsePDA,
           responder: responder.publicKey,
           admin: wallet.publicKey,
           adminAccount: adminAccountPDA,
@@ -178,7 +164,7 @@ describe("nodara - submit_response", () => {
         .rpc();
       assert.fail("Should fail: duplicate response");
     } catch (err) {
-      expect(err.message).to.include("already in use");
+      expect(err.error.errorCode.code).to.equal("ResponderLimitReached");
     }
   });
 });
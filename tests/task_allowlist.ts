@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import { createTask, depositFunds, submitResponse, toCid } from "./test-utils";
+
+const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+const hashPair = (a: Buffer, b: Buffer) => (Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a));
+
+// Builds a sorted-pair Merkle tree and returns the root plus a proof for every leaf
+const buildAllowlist = (members: PublicKey[]) => {
+  let level = members.map((m) => sha256(m.toBuffer()));
+  const proofs: Buffer[][] = level.map(() => []);
+  let positions = level.map((_, i) => i);
+
+  while (level.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+    }
+    positions = positions.map((pos, leaf) => {
+      const sibling = pos ^ 1;
+      if (sibling < level.length) proofs[leaf].push(level[sibling]);
+      return pos >> 1;
+    });
+    level = next;
+  }
+
+  return { root: level[0], proofs: proofs.map((p) => p.map((node) => Array.from(node))) };
+};
+
+describe("nodara - task_allowlist", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+
+  const allowlistedTask = async (members: PublicKey[]) => {
+    const task = await createTask(wallet.publicKey, program);
+    await depositFunds(
+      task.taskId,
+      task.taskPDA,
+      task.vaultPDA,
+      task.rewardPerResponse.mul(new anchor.BN(task.maxResponses * 2)),
+      program
+    );
+
+    const allowlist = buildAllowlist(members);
+    await program.methods
+      .setTaskAllowlist(task.taskId, Array.from(allowlist.root))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    return { ...task, allowlist };
+  };
+
+  it("Accepts a responder with a valid proof", async () => {
+    const members = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const { taskPDA, allowlist } = await allowlistedTask(members.map((m) => m.publicKey));
+
+    await submitResponse(taskPDA, members[2], toCid("Allowed"), program, provider, allowlist.proofs[2]);
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.equal(task.responsesReceived, 1);
+  });
+
+  it("Rejects a responder outside the allowlist", async () => {
+    const members = [Keypair.generate(), Keypair.generate()];
+    const { taskPDA, allowlist } = await allowlistedTask(members.map((m) => m.publicKey));
+
+    try {
+      await submitResponse(taskPDA, Keypair.generate(), toCid("Outsider"), program, provider, allowlist.proofs[0]);
+      assert.fail("Expected NotAllowlisted");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("NotAllowlisted");
+    }
+  });
+
+  it("Accepts any responder once the allowlist is cleared", async () => {
+    const { taskId, taskPDA } = await allowlistedTask([Keypair.generate().publicKey]);
+
+    await program.methods
+      .setTaskAllowlist(taskId, null)
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    await submitResponse(taskPDA, Keypair.generate(), toCid("Open"), program, provider);
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.isNull(task.allowlistRoot);
+  });
+});
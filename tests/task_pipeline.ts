@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  cidDigest,
+  createTask,
+  depositFunds,
+  generateAdminPDA,
+  generateResponsePDA,
+  setDisputeWindow,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - task_pipeline", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const fundedTask = async (responses: number) => {
+    const task = await createTask(wallet.publicKey, program, responses);
+    await depositFunds(task.taskId, task.taskPDA, task.vaultPDA, task.rewardPerResponse.muln(responses * 2), program);
+    return task;
+  };
+
+  // Capture photo -> label photo
+  const pipeline = async (captureDisputeWindow = 0) => {
+    const capture = await fundedTask(1);
+    await setDisputeWindow(capture.taskId, captureDisputeWindow, program);
+    const label = await fundedTask(3);
+    await program.methods
+      .setTaskPrerequisites(label.taskId, [capture.taskPDA])
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+    return { capture, label };
+  };
+
+  const submitWithPrerequisites = async (taskPDA: PublicKey, cid: string, prerequisites: PublicKey[]) => {
+    const responder = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(responder.publicKey, 0.1 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .submitResponse(cid, [], null)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: generateResponsePDA(taskPDA, responder.publicKey, program)[0],
+        responder: responder.publicKey,
+        admin: adminAuthority,
+        adminAccount: adminAccountPDA,
+      })
+      .remainingAccounts(prerequisites.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+      .rpc();
+  };
+
+  it("Opens the dependent task once the prerequisite completes", async () => {
+    const { capture, label } = await pipeline();
+
+    try {
+      await submitWithPrerequisites(label.taskPDA, toCid("EarlyLabel"), [capture.taskPDA]);
+      assert.fail("Expected PrerequisiteNotComplete");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PrerequisiteNotComplete");
+    }
+
+    const photographer = Keypair.generate();
+    await submitResponse(capture.taskPDA, photographer, toCid("Photo"), program, provider);
+    const [photoPDA] = generateResponsePDA(capture.taskPDA, photographer.publicKey, program);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: capture.taskPDA, responseAccount: photoPDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    await program.methods
+      .linkPrerequisiteOutput(label.taskId)
+      .accountsPartial({
+        prerequisiteTask: capture.taskPDA,
+        prerequisiteResponse: photoPDA,
+        creator: wallet.publicKey,
+      })
+      .rpc();
+
+    await submitWithPrerequisites(label.taskPDA, toCid("Label"), [capture.taskPDA]);
+
+    const task = await program.account.taskAccount.fetch(label.taskPDA);
+    assert.equal(task.responsesReceived, 1);
+    assert.deepEqual(task.prerequisiteOutputs[0].digest, cidDigest("Photo"));
+  });
+
+  it("Fails to link an output that can still be disputed", async () => {
+    const { capture, label } = await pipeline(86_400);
+
+    const photographer = Keypair.generate();
+    await submitResponse(capture.taskPDA, photographer, toCid("DisputablePhoto"), program, provider);
+    const [photoPDA] = generateResponsePDA(capture.taskPDA, photographer.publicKey, program);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: capture.taskPDA, responseAccount: photoPDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    try {
+      await program.methods
+        .linkPrerequisiteOutput(label.taskId)
+        .accountsPartial({
+          prerequisiteTask: capture.taskPDA,
+          prerequisiteResponse: photoPDA,
+          creator: wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("Expected DisputeWindowOpen");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("DisputeWindowOpen");
+    }
+  });
+
+  it("Fails when the prerequisite accounts are left out", async () => {
+    const { label } = await pipeline();
+
+    try {
+      await submitWithPrerequisites(label.taskPDA, toCid("NoPrerequisite"), []);
+      assert.fail("Expected PrerequisiteMissing");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("PrerequisiteMissing");
+    }
+  });
+
+  it("Fails with a task listed as its own prerequisite", async () => {
+    const task = await createTask(wallet.publicKey, program);
+
+    try {
+      await program.methods
+        .setTaskPrerequisites(task.taskId, [task.taskPDA])
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      assert.fail("Expected InvalidPrerequisite");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidPrerequisite");
+    }
+  });
+});
@@ -1,8 +1,35 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
 import { SmartContracts } from "../target/types/smart_contracts";
 
+const BASE32_ALPHABET = "abcdefghijklmnopqrstuvwxyz234567";
+
+// Digest stored on-chain for a CID built by toCid
+export const cidDigest = (label: string) => Array.from(createHash("sha256").update(label).digest());
+
+// CIDv1 (raw, sha2-256) of the label in base32 multibase
+export const toCid = (label: string) => {
+  const bytes = Buffer.concat([Buffer.from([0x01, 0x55, 0x12, 0x20]), Buffer.from(cidDigest(label))]);
+  let cid = "b";
+  let buffer = 0;
+  let bits = 0;
+
+  for (const byte of bytes) {
+    buffer = (buffer << 8) | byte;
+    bits += 8;
+    while (bits >= 5) {
+      bits -= 5;
+      cid += BASE32_ALPHABET[(buffer >> bits) & 31];
+    }
+    buffer &= (1 << bits) - 1;
+  }
+  if (bits > 0) cid += BASE32_ALPHABET[(buffer << (5 - bits)) & 31];
+
+  return cid;
+};
+
 export const generateTaskPDA = (creator: PublicKey, taskId: anchor.BN, program: Program<SmartContracts>) =>
   PublicKey.findProgramAddressSync(
     [Buffer.from("task"), creator.toBuffer(), Buffer.from(taskId.toArray("le", 8))],
@@ -15,12 +42,55 @@ export const generateVaultPDA = (taskPDA: PublicKey, program: Program<SmartContr
 export const generateAdminPDA = (program: Program<SmartContracts>) =>
   PublicKey.findProgramAddressSync([Buffer.from("admin")], program.programId);
 
+export const generateResponderPDA = (responder: PublicKey, program: Program<SmartContracts>) =>
+  PublicKey.findProgramAddressSync([Buffer.from("responder"), responder.toBuffer()], program.programId);
+
+export const generateResponsePDA = (
+  taskPDA: PublicKey,
+  responder: PublicKey,
+  program: Program<SmartContracts>,
+  index = 0
+) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("response"), taskPDA.toBuffer(), responder.toBuffer(), Buffer.from(new anchor.BN(index).toArray("le", 2))],
+    program.programId
+  );
+
+export const generateResponderTaskPDA = (taskPDA: PublicKey, responder: PublicKey, program: Program<SmartContracts>) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("responder_task"), taskPDA.toBuffer(), responder.toBuffer()],
+    program.programId
+  );
+
+export const generateCreatorProfilePDA = (creator: PublicKey, program: Program<SmartContracts>) =>
+  PublicKey.findProgramAddressSync([Buffer.from("creator"), creator.toBuffer()], program.programId);
+
+// Disbursements credit the creator profile whenever it exists
+export const creatorProfileAccounts = (creator: PublicKey, program: Program<SmartContracts>) => ({
+  taskCreator: creator,
+  creatorProfile: generateCreatorProfilePDA(creator, program)[0],
+});
+
+export const generateProtocolStatsPDA = (program: Program<SmartContracts>) =>
+  PublicKey.findProgramAddressSync([Buffer.from("protocol_stats")], program.programId);
+
+// create_task takes the profile only once it exists, create_task_auto makes it
+export const createTaskAccounts = async (creator: PublicKey, program: Program<SmartContracts>) => {
+  const [profilePDA] = generateCreatorProfilePDA(creator, program);
+  const profile = await program.account.creatorProfile.fetchNullable(profilePDA);
+  return {
+    creator,
+    creatorProfile: profile ? profilePDA : null,
+    protocolStats: generateProtocolStatsPDA(program)[0],
+  };
+};
+
 export const validTaskInput = async (publicKey: PublicKey, program: Program<SmartContracts>) => {
   const taskId = new anchor.BN(Math.floor(Math.random() * 1_000_000));
   const rewardPerResponse = new anchor.BN(100_000); // 0.1 SOL
   const maxResponses = 5;
   const deadline = new anchor.BN(Math.floor(Date.now() / 1000 + 3600)); // 1 hour later
-  const cid = "QmValidCIDHash";
+  const cid = toCid("ValidCIDHash");
   const [taskPDA, bump] = generateTaskPDA(publicKey, taskId, program);
 
   return {
@@ -44,14 +114,14 @@ export const createTask = async (
   const rewardPerResponse = new anchor.BN(100_000);
   const maxResponses = responses || 10;
   const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
-  const cid = "QmCID" + taskId.toString();
+  const cid = toCid("CID" + taskId.toString());
 
   const [taskPDA] = generateTaskPDA(publicKey, taskId, program);
   const [vaultPDA] = generateVaultPDA(taskPDA, program);
 
   await program.methods
     .createTask(taskId, rewardPerResponse, maxResponses, deadline, cid)
-    .accounts({ creator: publicKey })
+    .accountsPartial(await createTaskAccounts(publicKey, program))
     .rpc();
 
   return { taskId, taskPDA, vaultPDA, maxResponses, rewardPerResponse };
@@ -80,11 +150,37 @@ export const depositFunds = async (
     .rpc();
 };
 
-export const submitResponse = async (taskPDA: PublicKey, responder: Keypair, cid: string, program: Program<SmartContracts>, provider: anchor.Provider) => {
-  const [responsePDA] = PublicKey.findProgramAddressSync(
-    [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-    program.programId
-  );
+// Lets responses be paid as soon as they are verified
+export const setDisputeWindow = async (taskId: anchor.BN, seconds: number, program: Program<SmartContracts>) => {
+  await program.methods
+    .setDisputeWindow(taskId, new anchor.BN(seconds))
+    .accounts({ creator: program.provider.wallet.publicKey })
+    .rpc();
+};
+
+// Deposits exactly what the task still needs to open for submissions
+export const fundTask = async (
+  taskId: anchor.BN,
+  taskPDA: PublicKey,
+  vaultPDA: PublicKey,
+  program: Program<SmartContracts>,
+) => {
+  const required = await program.methods.requiredFunding().accounts({ taskAccount: taskPDA }).view();
+  await depositFunds(taskId, taskPDA, vaultPDA, required, program);
+  return required as anchor.BN;
+};
+
+export const submitResponse = async (
+  taskPDA: PublicKey,
+  responder: Keypair,
+  cid: string,
+  program: Program<SmartContracts>,
+  provider: anchor.Provider,
+  proof: number[][] = [],
+  value: anchor.BN | null = null,
+  index = 0
+) => {
+  const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program, index);
   const [adminAccountPDA] = generateAdminPDA(program);
   const adminAuthority = provider.wallet.publicKey;
 
@@ -93,7 +189,7 @@ export const submitResponse = async (taskPDA: PublicKey, responder: Keypair, cid
   await provider.connection.confirmTransaction(sig);
 
   await program.methods
-    .submitResponse(cid)
+    .submitResponse(cid, proof, value)
     .accountsPartial({
       taskAccount: taskPDA,
       responseAccount: responsePDA,
@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  createTask,
+  fundTask,
+  generateAdminPDA,
+  generateCreatorProfilePDA,
+  generateResponsePDA,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - tip_responder", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+  const [profilePDA] = generateCreatorProfilePDA(wallet.publicKey, program);
+
+  // Tips are only tracked on an existing profile, which create_task_auto makes
+  before(async () => {
+    if (!(await program.account.creatorProfile.fetchNullable(profilePDA))) {
+      await program.methods
+        .createTaskAuto(new anchor.BN(100_000), 1, new anchor.BN(Math.floor(Date.now() / 1000) + 3600), toCid("TipProfile"))
+        .accountsPartial({ creator: wallet.publicKey })
+        .rpc();
+    }
+  });
+
+  const submittedResponse = async (label: string) => {
+    const { taskId, taskPDA, vaultPDA } = await createTask(wallet.publicKey, program, 1);
+    await fundTask(taskId, taskPDA, vaultPDA, program);
+
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid(label), program, provider);
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
+    return { taskPDA, vaultPDA, responder, responsePDA };
+  };
+
+  const tip = (taskPDA: PublicKey, responsePDA: PublicKey, responder: PublicKey, amount: anchor.BN) =>
+    program.methods
+      .tipResponder(amount)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: responsePDA,
+        recipient: responder,
+        creatorProfile: profilePDA,
+        adminAccount: adminAccountPDA,
+        adminAuthority,
+        creator: wallet.publicKey,
+      })
+      .rpc();
+
+  it("Tips a verified response through the vault", async () => {
+    const { taskPDA, vaultPDA, responder, responsePDA } = await submittedResponse("Exceptional");
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    const amount = new anchor.BN(0.01 * LAMPORTS_PER_SOL);
+    const fee = amount.muln(69).divn(1000);
+    const net = amount.sub(fee);
+
+    const vaultBefore = await program.account.rewardVault.fetch(vaultPDA);
+    const profileBefore = await program.account.creatorProfile.fetch(profilePDA);
+    const responderBefore = await provider.connection.getBalance(responder.publicKey);
+
+    await tip(taskPDA, responsePDA, responder.publicKey, amount);
+
+    const responderAfter = await provider.connection.getBalance(responder.publicKey);
+    const vaultAfter = await program.account.rewardVault.fetch(vaultPDA);
+    const response = await program.account.responseAccount.fetch(responsePDA);
+
+    assert.equal(responderAfter - responderBefore, net.toNumber());
+    assert.equal(response.tipped.toString(), net.toString());
+    assert.equal(vaultAfter.balance.toString(), vaultBefore.balance.toString());
+
+    // Tips are counted apart from escrowed deposits
+    const profileAfter = await program.account.creatorProfile.fetch(profilePDA);
+    assert.equal(profileAfter.lamportsTipped.sub(profileBefore.lamportsTipped).toString(), net.toString());
+    assert.equal(profileAfter.feesPaid.sub(profileBefore.feesPaid).toString(), fee.toString());
+    assert.equal(profileAfter.lamportsEscrowed.toString(), profileBefore.lamportsEscrowed.toString());
+  });
+
+  it("Fails to tip an unverified response", async () => {
+    const { taskPDA, responder, responsePDA } = await submittedResponse("Unverified");
+
+    try {
+      await tip(taskPDA, responsePDA, responder.publicKey, new anchor.BN(0.01 * LAMPORTS_PER_SOL));
+      assert.fail("Expected ResponseNotVerified");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponseNotVerified");
+    }
+  });
+});
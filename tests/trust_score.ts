@@ -0,0 +1,151 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { assert, expect } from "chai";
+import { SmartContracts } from "../target/types/smart_contracts";
+import {
+  createTask,
+  depositFunds,
+  generateAdminPDA,
+  generateResponsePDA,
+  generateResponderPDA,
+  submitResponse,
+  toCid,
+} from "./test-utils";
+
+describe("nodara - trust_score", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.smartContracts as Program<SmartContracts>;
+  const provider = anchor.getProvider();
+  const wallet = provider.wallet;
+  const adminAuthority = wallet.publicKey;
+
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const fundedTask = async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(
+      wallet.publicKey,
+      program
+    );
+    await depositFunds(
+      taskId,
+      taskPDA,
+      vaultPDA,
+      rewardPerResponse.mul(new anchor.BN(maxResponses * 2)),
+      program
+    );
+    return { taskId, taskPDA };
+  };
+
+  const responsePDAFor = (taskPDA: PublicKey, responder: Keypair) =>
+    generateResponsePDA(taskPDA, responder.publicKey, program)[0];
+
+  it("Starts new responders at the initial score", async () => {
+    const { taskPDA } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("TrustInit"), program, provider);
+
+    const [responderPDA] = generateResponderPDA(responder.publicKey, program);
+    const account = await program.account.responder.fetch(responderPDA);
+    assert.equal(account.trustScore, 5_000);
+    assert.equal(account.responsesSubmitted, 1);
+  });
+
+  it("Raises the score on verification and lowers it on rejection", async () => {
+    const responder = Keypair.generate();
+    const [responderPDA] = generateResponderPDA(responder.publicKey, program);
+
+    const first = await fundedTask();
+    await submitResponse(first.taskPDA, responder, toCid("TrustGood"), program, provider);
+    await program.methods
+      .verifyResponse(null)
+      .accountsPartial({
+        taskAccount: first.taskPDA,
+        responseAccount: responsePDAFor(first.taskPDA, responder),
+        responderAccount: responderPDA,
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+    let account = await program.account.responder.fetch(responderPDA);
+    assert.equal(account.responsesAccepted, 1);
+    assert.equal(account.trustScore, 5_500);
+
+    const second = await fundedTask();
+    await submitResponse(second.taskPDA, responder, toCid("TrustLate"), program, provider);
+    await program.methods
+      .rejectResponse({ late: {} })
+      .accountsPartial({
+        taskAccount: second.taskPDA,
+        responseAccount: responsePDAFor(second.taskPDA, responder),
+        responderAccount: responderPDA,
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+    account = await program.account.responder.fetch(responderPDA);
+    assert.equal(account.responsesRejected, 1);
+    assert.equal(account.responsesLate, 1);
+    assert.equal(account.trustScore, 4_950);
+
+    const response = await program.account.responseAccount.fetch(
+      responsePDAFor(second.taskPDA, responder)
+    );
+    assert.isTrue(response.isRejected);
+    assert.isFalse(response.isVerified);
+  });
+
+  it("Fails to verify a rejected response", async () => {
+    const { taskPDA } = await fundedTask();
+    const responder = Keypair.generate();
+    await submitResponse(taskPDA, responder, toCid("TrustRejected"), program, provider);
+    const responsePDA = responsePDAFor(taskPDA, responder);
+
+    await program.methods
+      .rejectResponse({ invalid: {} })
+      .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+      .rpc();
+
+    try {
+      await program.methods
+        .verifyResponse(null)
+        .accountsPartial({ taskAccount: taskPDA, responseAccount: responsePDA, adminAccount: adminAccountPDA, signer: adminAuthority })
+        .rpc();
+      assert.fail("Expected ResponseAlreadyRejected");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ResponseAlreadyRejected");
+    }
+  });
+
+  it("Enforces the task minimum trust score", async () => {
+    const { taskId, taskPDA } = await fundedTask();
+
+    await program.methods
+      .setMinTrustScore(taskId, 6_000)
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    try {
+      await submitResponse(taskPDA, Keypair.generate(), toCid("TrustLow"), program, provider);
+      assert.fail("Expected TrustScoreTooLow");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("TrustScoreTooLow");
+    }
+  });
+
+  it("Fails with a minimum trust score above the maximum", async () => {
+    const { taskId } = await fundedTask();
+
+    try {
+      await program.methods
+        .setMinTrustScore(taskId, 10_001)
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      assert.fail("Expected InvalidTrustScore");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("InvalidTrustScore");
+    }
+  });
+});
@@ -1,14 +1,29 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
-import { validTaskInput, createTask } from "./test-utils";
+import {
+  validTaskInput,
+  createTask,
+  cidDigest,
+  depositFunds,
+  generateAdminPDA,
+  submitResponse,
+  toCid,
+} from "./test-utils";
 
 describe("nodara - update_task", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
   const program = anchor.workspace.smartContracts as Program<SmartContracts>;
   const provider = anchor.getProvider();
   const wallet = provider.wallet;
+  const [adminAccountPDA] = generateAdminPDA(program);
+
+  const futureDeadline = () => new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+  // Deposits are charged the 6.9% platform fee, so gross up to escrow `net`
+  const grossUp = (net: anchor.BN) => net.muln(1000).divn(931).addn(1);
 
   it("Updates a task successfully", async () => {
     // First, create the task
@@ -18,7 +33,7 @@ describe("nodara - update_task", () => {
     const newRewardPerResponse = new anchor.BN(200_000);
     const newMaxResponses = 20;
     const newDeadline = new anchor.BN(Math.floor(Date.now() / 1000) + 7200);
-    const newCid = "QmUpdatedCIDHash";
+    const newCid = toCid("UpdatedCIDHash");
 
     await program.methods
       .updateTask(taskId, newRewardPerResponse, newMaxResponses, newDeadline, newCid)
@@ -31,7 +46,7 @@ describe("nodara - update_task", () => {
     assert.strictEqual(updatedTask.rewardPerResponse.toNumber(), newRewardPerResponse.toNumber());
     assert.strictEqual(updatedTask.maxResponses, newMaxResponses);
     assert.strictEqual(updatedTask.deadline.toNumber(), newDeadline.toNumber());
-    assert.strictEqual(updatedTask.cid, newCid);
+    assert.deepEqual(updatedTask.cid.digest, cidDigest("UpdatedCIDHash"));
   });
 
   it("Fails with task_id = 0", async () => {
@@ -56,7 +71,7 @@ describe("nodara - update_task", () => {
 
     try {
       await program.methods
-        .updateTask(taskId, new anchor.BN(0), 5, deadline, "QmCID")
+        .updateTask(taskId, new anchor.BN(0), 5, deadline, toCid("CID"))
         .accounts({ creator: wallet.publicKey })
         .rpc();
       expect.fail("Expected InvalidReward but it succeeded.");
@@ -71,7 +86,7 @@ describe("nodara - update_task", () => {
 
     try {
       await program.methods
-        .updateTask(taskId, rewardPerResponse, 0, deadline, "QmCID")
+        .updateTask(taskId, rewardPerResponse, 0, deadline, toCid("CID"))
         .accounts({ creator: wallet.publicKey })
         .rpc();
       expect.fail("Expected InvalidMaxResponses but it succeeded.");
@@ -86,7 +101,7 @@ describe("nodara - update_task", () => {
 
     try {
       await program.methods
-        .updateTask(taskId, rewardPerResponse, 5, pastDeadline, "QmCID")
+        .updateTask(taskId, rewardPerResponse, 5, pastDeadline, toCid("CID"))
         .accounts({ creator: wallet.publicKey })
         .rpc();
       expect.fail("Expected InvalidDeadline but it succeeded.");
@@ -124,4 +139,101 @@ describe("nodara - update_task", () => {
       expect(err.message).to.match(/AccountNotInitialized./i);
     }
   });
+
+  it("Emits a TaskUpdated event with the old and new values", async () => {
+    const { taskId, taskPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program);
+    const newDeadline = futureDeadline();
+
+    let event = null;
+    const listener = program.addEventListener("taskUpdated", (e) => (event = e));
+    await program.methods
+      .updateTask(taskId, rewardPerResponse, maxResponses, newDeadline, toCid("EventCID"))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    assert.isNotNull(event);
+    assert.ok(event.task.equals(taskPDA));
+    assert.equal(event.newDeadline.toString(), newDeadline.toString());
+    assert.deepEqual(event.newCid.digest, cidDigest("EventCID"));
+  });
+
+  it("Fails to update a completed task", async () => {
+    const { taskId, taskPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program);
+    await program.methods
+      .markTaskComplete()
+      .accountsPartial({ taskAccount: taskPDA, adminAccount: adminAccountPDA, signer: wallet.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .updateTask(taskId, rewardPerResponse, maxResponses, futureDeadline(), toCid("CID"))
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      expect.fail("Expected TaskAlreadyComplete but it succeeded.");
+    } catch (err) {
+      expect(err.message).to.match(/TaskAlreadyComplete/);
+    }
+  });
+
+  it("Fails to lower max responses below the responses received", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await createTask(wallet.publicKey, program, 3);
+    await depositFunds(taskId, taskPDA, vaultPDA, grossUp(rewardPerResponse.muln(3)), program);
+    await submitResponse(taskPDA, Keypair.generate(), toCid("First"), program, provider);
+    await submitResponse(taskPDA, Keypair.generate(), toCid("Second"), program, provider);
+
+    try {
+      await program.methods
+        .updateTask(taskId, rewardPerResponse, 1, futureDeadline(), toCid("CID"))
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      expect.fail("Expected MaxResponsesBelowReceived but it succeeded.");
+    } catch (err) {
+      expect(err.message).to.match(/MaxResponsesBelowReceived/);
+    }
+  });
+
+  it("Fails to change the reward once responses arrived", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse, maxResponses } = await createTask(wallet.publicKey, program, 2);
+    await depositFunds(taskId, taskPDA, vaultPDA, grossUp(rewardPerResponse.muln(4)), program);
+    await submitResponse(taskPDA, Keypair.generate(), toCid("Answer"), program, provider);
+
+    // Existing locks were sized from the old reward, so raising it is refused too
+    for (const reward of [rewardPerResponse.divn(2), rewardPerResponse.muln(2)]) {
+      try {
+        await program.methods
+          .updateTask(taskId, reward, maxResponses, futureDeadline(), toCid("CID"))
+          .accounts({ creator: wallet.publicKey })
+          .rpc();
+        expect.fail("Expected RewardChangeNotAllowed but it succeeded.");
+      } catch (err) {
+        expect(err.message).to.match(/RewardChangeNotAllowed/);
+      }
+    }
+  });
+
+  it("Requires extra escrow before raising a funded task's liability", async () => {
+    const { taskId, taskPDA, vaultPDA, rewardPerResponse } = await createTask(wallet.publicKey, program, 2);
+    await depositFunds(taskId, taskPDA, vaultPDA, grossUp(rewardPerResponse.muln(2)), program);
+
+    try {
+      await program.methods
+        .updateTask(taskId, rewardPerResponse, 4, futureDeadline(), toCid("CID"))
+        .accounts({ creator: wallet.publicKey })
+        .rpc();
+      expect.fail("Expected InsufficientVaultBalance but it succeeded.");
+    } catch (err) {
+      expect(err.message).to.match(/InsufficientVaultBalance/);
+    }
+
+    await depositFunds(taskId, taskPDA, vaultPDA, grossUp(rewardPerResponse.muln(2)), program);
+    await program.methods
+      .updateTask(taskId, rewardPerResponse, 4, futureDeadline(), toCid("CID"))
+      .accounts({ creator: wallet.publicKey })
+      .rpc();
+
+    const task = await program.account.taskAccount.fetch(taskPDA);
+    assert.strictEqual(task.maxResponses, 4);
+  });
 });
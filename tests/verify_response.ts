@@ -4,9 +4,12 @@ import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import { SmartContracts } from "../target/types/smart_contracts";
 import {
+  createTaskAccounts,
   generateAdminPDA,
+  generateResponsePDA,
   generateTaskPDA,
   generateVaultPDA,
+  toCid,
 } from "./test-utils";
 
 describe("nodara - verify_response", () => {
@@ -23,7 +26,7 @@ describe("nodara - verify_response", () => {
     const rewardPerResponse = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
     const maxResponses = 1;
     const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 600);
-    const cid = "QmTaskCID" + taskId.toString();
+    const cid = toCid("TaskCID" + taskId.toString());
     const creator = wallet.publicKey;
 
     const [taskPDA] = generateTaskPDA(creator, taskId, program);
@@ -31,10 +34,10 @@ describe("nodara - verify_response", () => {
 
     await program.methods
       .createTask(taskId, rewardPerResponse, maxResponses, deadline, cid)
-      .accounts({ creator })
+      .accountsPartial(await createTaskAccounts(creator, program))
       .rpc();
 
-    const totalDeposit = rewardPerResponse.mul(new anchor.BN(maxResponses));
+    const totalDeposit = await program.methods.requiredFunding().accounts({ taskAccount: taskPDA }).view();
     await program.methods
       .depositFunds(taskId, totalDeposit)
       .accountsPartial({
@@ -46,20 +49,17 @@ describe("nodara - verify_response", () => {
       })
       .rpc();
 
-    return { taskId, taskPDA };
+    return { taskId, taskPDA, vaultPDA, rewardPerResponse };
   };
 
   const submitResponse = async (taskPDA: PublicKey, responder: Keypair, cid: string) => {
-    const [responsePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("response"), taskPDA.toBuffer(), responder.publicKey.toBuffer()],
-      program.programId
-    );
+    const [responsePDA] = generateResponsePDA(taskPDA, responder.publicKey, program);
 
     const sig = await provider.connection.requestAirdrop(responder.publicKey, 0.1 * LAMPORTS_PER_SOL);
     await provider.connection.confirmTransaction(sig);
 
     await program.methods
-      .submitResponse(cid)
+      .submitResponse(cid, [], null)
       .accountsPartial({
         taskAccount: taskPDA,
         responseAccount: responsePDA,
@@ -75,11 +75,12 @@ describe("nodara - verify_response", () => {
   it("Verifies a response successfully", async () => {
     const { taskPDA } = await createTaskWithDeposit();
     const responder = Keypair.generate();
-    const responsePDA = await submitResponse(taskPDA, responder, "QmResVerify");
+    const responsePDA = await submitResponse(taskPDA, responder, toCid("ResVerify"));
 
     await program.methods
-      .verifyResponse()
+      .verifyResponse(null)
       .accountsPartial({
+        taskAccount: taskPDA,
         responseAccount: responsePDA,
         adminAccount: adminAccountPDA,
         signer: adminAuthority,
@@ -94,15 +95,16 @@ describe("nodara - verify_response", () => {
     const { taskPDA } = await createTaskWithDeposit();
     const responder = Keypair.generate();
     const intruder = Keypair.generate();
-    const responsePDA = await submitResponse(taskPDA, responder, "QmBadGuy");
+    const responsePDA = await submitResponse(taskPDA, responder, toCid("BadGuy"));
 
     const sig = await provider.connection.requestAirdrop(intruder.publicKey, 0.1 * LAMPORTS_PER_SOL);
     await provider.connection.confirmTransaction(sig);
 
     try {
       await program.methods
-        .verifyResponse()
+        .verifyResponse(null)
         .accountsPartial({
+          taskAccount: taskPDA,
           responseAccount: responsePDA,
           adminAccount: adminAccountPDA,
           signer: intruder.publicKey,
@@ -119,11 +121,12 @@ describe("nodara - verify_response", () => {
   it("Allows verifying a response only once", async () => {
     const { taskPDA } = await createTaskWithDeposit();
     const responder = Keypair.generate();
-    const responsePDA = await submitResponse(taskPDA, responder, "QmOnce");
+    const responsePDA = await submitResponse(taskPDA, responder, toCid("Once"));
 
     await program.methods
-      .verifyResponse()
+      .verifyResponse(null)
       .accountsPartial({
+        taskAccount: taskPDA,
         responseAccount: responsePDA,
         adminAccount: adminAccountPDA,
         signer: adminAuthority,
@@ -135,8 +138,9 @@ describe("nodara - verify_response", () => {
 
     // Try verifying again (should succeed, idempotent)
     await program.methods
-      .verifyResponse()
+      .verifyResponse(null)
       .accountsPartial({
+        taskAccount: taskPDA,
         responseAccount: responsePDA,
         adminAccount: adminAccountPDA,
         signer: adminAuthority,
@@ -146,4 +150,48 @@ describe("nodara - verify_response", () => {
     const reFetched = await program.account.responseAccount.fetch(responsePDA);
     assert.isTrue(reFetched.isVerified, "Response should still be verified");
   });
+
+  it("Assigns a partial reward and unlocks the difference", async () => {
+    const { taskPDA, vaultPDA, rewardPerResponse } = await createTaskWithDeposit();
+    const responder = Keypair.generate();
+    const responsePDA = await submitResponse(taskPDA, responder, toCid("Partial"));
+    const graded = rewardPerResponse.divn(4);
+
+    await program.methods
+      .verifyResponse(graded)
+      .accountsPartial({
+        taskAccount: taskPDA,
+        responseAccount: responsePDA,
+        adminAccount: adminAccountPDA,
+        signer: adminAuthority,
+      })
+      .rpc();
+
+    const response = await program.account.responseAccount.fetch(responsePDA);
+    const vault = await program.account.rewardVault.fetch(vaultPDA);
+    assert.equal(response.owed.toString(), graded.toString());
+    assert.equal(vault.lockedBalance.toString(), graded.toString());
+  });
+
+  it("Fails to assign more than the reward per response", async () => {
+    const { taskPDA, rewardPerResponse } = await createTaskWithDeposit();
+    const responder = Keypair.generate();
+    const responsePDA = await submitResponse(taskPDA, responder, toCid("Excessive"));
+
+    try {
+      await program.methods
+        .verifyResponse(rewardPerResponse.addn(1))
+        .accountsPartial({
+          taskAccount: taskPDA,
+          responseAccount: responsePDA,
+          adminAccount: adminAccountPDA,
+          signer: adminAuthority,
+        })
+        .rpc();
+
+      assert.fail("Expected ExcessiveRewardAmount");
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal("ExcessiveRewardAmount");
+    }
+  });
 });